@@ -0,0 +1,6 @@
+-- One university ID backs at most one account. Enforced here rather than in the
+-- handler so two concurrent attestations cannot both pass a lookup.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS identity_hash BYTEA;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS identity_attested_at TIMESTAMPTZ;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_identity_hash ON users(identity_hash);
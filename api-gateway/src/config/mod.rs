@@ -13,6 +13,7 @@ pub struct Config {
     pub solana_rpc_url: String,
     pub solana_ws_url: String,
     pub engineering_api_key: String,
+    pub identity_salt: String,
     pub max_connections: u32,
     pub redis_pool_size: u32,
     pub request_timeout: u64,
@@ -45,6 +46,8 @@ impl Config {
                 .map_err(|_| anyhow::anyhow!("SOLANA_WS_URL environment variable is required"))?,
             engineering_api_key: env::var("ENGINEERING_API_KEY")
                 .map_err(|_| anyhow::anyhow!("ENGINEERING_API_KEY environment variable is required"))?,
+            identity_salt: env::var("IDENTITY_SALT")
+                .map_err(|_| anyhow::anyhow!("IDENTITY_SALT environment variable is required"))?,
             max_connections: env::var("MAX_CONNECTIONS")
                 .map_err(|_| anyhow::anyhow!("MAX_CONNECTIONS environment variable is required"))?
                 .parse()?,
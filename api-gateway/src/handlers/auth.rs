@@ -11,6 +11,7 @@ use crate::auth::{SecureAuthResponse, Claims, UserInfo, SecureUserInfo};
 use crate::auth::middleware::AuthenticatedUser;
 use crate::auth::password::PasswordService;
 use crate::error::{ApiError, Result};
+use crate::models::user::DelegatedMeter;
 use crate::AppState;
 
 /// Login request
@@ -62,6 +63,14 @@ pub struct UserSearchQuery {
     pub per_page: Option<u32>,
 }
 
+/// Current user's profile, including meters delegated to them
+#[derive(Debug, Serialize)]
+pub struct ProfileResponse {
+    #[serde(flatten)]
+    pub user: UserInfo,
+    pub delegated_meters: Vec<DelegatedMeter>,
+}
+
 /// Paginated user response
 #[derive(Debug, Serialize)]
 pub struct UserListResponse {
@@ -134,7 +143,7 @@ pub async fn login(
 pub async fn get_profile(
     State(state): State<AppState>,
     user: AuthenticatedUser,
-) -> Result<Json<UserInfo>> {
+) -> Result<Json<ProfileResponse>> {
     let user_data = sqlx::query_as::<_, UserRow>(
         "SELECT id, username, email, password_hash, role::text as role, department, 
                 first_name, last_name, wallet_address, blockchain_registered,
@@ -149,6 +158,22 @@ pub async fn get_profile(
 
     let user_data = user_data.ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
 
+    // Delegations are granted to wallets, so users without one cannot have any
+    let delegated_meters = match &user_data.wallet_address {
+        Some(wallet_address) => sqlx::query_as::<_, DelegatedMeter>(
+            "SELECT owner_wallet, meter_id, scopes, expires_at
+             FROM meter_delegations
+             WHERE delegate_wallet = $1 AND revoked_at IS NULL
+               AND (expires_at IS NULL OR expires_at > NOW())
+             ORDER BY created_at"
+        )
+        .bind(wallet_address)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Database error: {}", e)))?,
+        None => Vec::new(),
+    };
+
     let profile = UserInfo {
         id: user_data.id,
         username: user_data.username,
@@ -159,7 +184,10 @@ pub async fn get_profile(
         blockchain_registered: user_data.blockchain_registered,
     };
 
-    Ok(Json(profile))
+    Ok(Json(ProfileResponse {
+        user: profile,
+        delegated_meters,
+    }))
 }
 
 /// Update user profile
@@ -167,7 +195,7 @@ pub async fn update_profile(
     State(state): State<AppState>,
     user: AuthenticatedUser,
     Json(request): Json<UpdateProfileRequest>,
-) -> Result<Json<UserInfo>> {
+) -> Result<Json<ProfileResponse>> {
     // Validate request
     request.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
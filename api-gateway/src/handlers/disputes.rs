@@ -0,0 +1,229 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::auth::middleware::AuthenticatedUser;
+use crate::error::{ApiError, Result};
+use crate::services::dispute::{DisputeStatus, DisputeSubject};
+use crate::AppState;
+
+/// Request to dispute a trade or meter reading
+#[derive(Debug, Deserialize)]
+pub struct OpenDisputeRequest {
+    pub subject: DisputeSubject,
+    pub evidence_uris: Vec<String>,
+}
+
+/// Query parameters for listing disputes
+#[derive(Debug, Deserialize)]
+pub struct DisputeQuery {
+    pub status: Option<DisputeStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Dispute state as mirrored from the governance program (admin only)
+#[derive(Debug, Deserialize)]
+pub struct UpdateDisputeRequest {
+    pub on_chain_id: Option<i64>,
+    pub status: DisputeStatus,
+    pub uphold_votes: i32,
+    pub reject_votes: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DisputeResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Id of the governance `Dispute` account, once opened on-chain
+    pub on_chain_id: Option<i64>,
+    pub subject: DisputeSubject,
+    pub evidence_uris: Vec<String>,
+    pub status: DisputeStatus,
+    pub uphold_votes: i32,
+    pub reject_votes: i32,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DisputeRow {
+    id: Uuid,
+    user_id: Uuid,
+    on_chain_id: Option<i64>,
+    subject: String,
+    evidence_uris: String,
+    status: String,
+    uphold_votes: i32,
+    reject_votes: i32,
+    created_at: DateTime<Utc>,
+    resolved_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<DisputeRow> for DisputeResponse {
+    type Error = ApiError;
+
+    fn try_from(row: DisputeRow) -> Result<Self> {
+        let corrupt = |e: serde_json::Error| ApiError::Internal(format!("Corrupt dispute {}: {}", row.id, e));
+        Ok(DisputeResponse {
+            id: row.id,
+            user_id: row.user_id,
+            on_chain_id: row.on_chain_id,
+            subject: serde_json::from_str(&row.subject).map_err(corrupt)?,
+            evidence_uris: serde_json::from_str(&row.evidence_uris).map_err(corrupt)?,
+            status: serde_json::from_value(serde_json::Value::String(row.status.clone())).map_err(corrupt)?,
+            uphold_votes: row.uphold_votes,
+            reject_votes: row.reject_votes,
+            created_at: row.created_at,
+            resolved_at: row.resolved_at,
+        })
+    }
+}
+
+const DISPUTE_COLUMNS: &str =
+    "id, user_id, on_chain_id, subject, evidence_uris, status, uphold_votes, reject_votes, created_at, resolved_at";
+
+async fn load_dispute(state: &AppState, dispute_id: Uuid) -> Result<DisputeRow> {
+    sqlx::query_as::<_, DisputeRow>(&format!("SELECT {} FROM disputes WHERE id = $1", DISPUTE_COLUMNS))
+        .bind(dispute_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch dispute: {}", e);
+            ApiError::Database(e)
+        })?
+        .ok_or_else(|| ApiError::NotFound("Dispute not found".to_string()))
+}
+
+/// Open a dispute against a trade or meter reading
+/// POST /api/v1/disputes
+pub async fn open_dispute(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(payload): Json<OpenDisputeRequest>,
+) -> Result<Json<DisputeResponse>> {
+    payload.subject.validate(&payload.evidence_uris).map_err(ApiError::Validation)?;
+
+    let encode = |e: serde_json::Error| ApiError::Internal(format!("Failed to encode dispute: {}", e));
+    let subject = serde_json::to_string(&payload.subject).map_err(encode)?;
+    let evidence_uris = serde_json::to_string(&payload.evidence_uris).map_err(encode)?;
+
+    let row = sqlx::query_as::<_, DisputeRow>(&format!(
+        "INSERT INTO disputes (id, user_id, subject, evidence_uris, status, uphold_votes, reject_votes, created_at) VALUES ($1, $2, $3, $4, $5, 0, 0, $6) RETURNING {}",
+        DISPUTE_COLUMNS
+    ))
+    .bind(Uuid::new_v4())
+    .bind(user.0.sub)
+    .bind(subject)
+    .bind(evidence_uris)
+    .bind(DisputeStatus::Voting.as_str())
+    .bind(Utc::now())
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store dispute: {}", e);
+        ApiError::Database(e)
+    })?;
+
+    // TODO: In Phase 4, submit governance open_dispute from the user's wallet with the bond
+
+    tracing::info!("Dispute {} opened by {}", row.id, user.0.sub);
+
+    Ok(Json(row.try_into()?))
+}
+
+/// List the user's disputes, or all disputes for admins
+/// GET /api/v1/disputes
+pub async fn list_disputes(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(params): Query<DisputeQuery>,
+) -> Result<Json<Vec<DisputeResponse>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
+    let owner = if user.0.has_role("admin") { None } else { Some(user.0.sub) };
+
+    let rows = sqlx::query_as::<_, DisputeRow>(&format!(
+        "SELECT {} FROM disputes WHERE ($1::uuid IS NULL OR user_id = $1) AND ($2::text IS NULL OR status = $2) ORDER BY created_at DESC LIMIT $3 OFFSET $4",
+        DISPUTE_COLUMNS
+    ))
+    .bind(owner)
+    .bind(params.status.map(|status| status.as_str()))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch disputes: {}", e);
+        ApiError::Database(e)
+    })?;
+
+    let disputes = rows
+        .into_iter()
+        .map(DisputeResponse::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Json(disputes))
+}
+
+/// Track one dispute (its claimant or an admin)
+/// GET /api/v1/disputes/:id
+pub async fn get_dispute(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(dispute_id): Path<Uuid>,
+) -> Result<Json<DisputeResponse>> {
+    let row = load_dispute(&state, dispute_id).await?;
+    if row.user_id != user.0.sub && !user.0.has_role("admin") {
+        return Err(ApiError::NotFound("Dispute not found".to_string()));
+    }
+
+    Ok(Json(row.try_into()?))
+}
+
+/// Record votes and the outcome seen on-chain for a dispute (admin only)
+/// PUT /api/v1/disputes/:id
+pub async fn update_dispute(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(dispute_id): Path<Uuid>,
+    Json(payload): Json<UpdateDisputeRequest>,
+) -> Result<Json<DisputeResponse>> {
+    if !user.0.has_role("admin") {
+        return Err(ApiError::Authorization("Admin access required".to_string()));
+    }
+    if payload.uphold_votes < 0 || payload.reject_votes < 0 {
+        return Err(ApiError::Validation("Vote counts cannot be negative".to_string()));
+    }
+
+    let current = load_dispute(&state, dispute_id).await?;
+    if current.status != DisputeStatus::Voting.as_str() {
+        return Err(ApiError::Conflict("Dispute is already resolved".to_string()));
+    }
+
+    let resolved_at = (payload.status != DisputeStatus::Voting).then(Utc::now);
+    let row = sqlx::query_as::<_, DisputeRow>(&format!(
+        "UPDATE disputes SET on_chain_id = COALESCE($2, on_chain_id), status = $3, uphold_votes = $4, reject_votes = $5, resolved_at = $6 WHERE id = $1 RETURNING {}",
+        DISPUTE_COLUMNS
+    ))
+    .bind(dispute_id)
+    .bind(payload.on_chain_id)
+    .bind(payload.status.as_str())
+    .bind(payload.uphold_votes)
+    .bind(payload.reject_votes)
+    .bind(resolved_at)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update dispute: {}", e);
+        ApiError::Database(e)
+    })?;
+
+    tracing::info!("Dispute {} is now {}", dispute_id, payload.status.as_str());
+
+    Ok(Json(row.try_into()?))
+}
@@ -11,6 +11,7 @@ use crate::{
     auth::middleware::AuthenticatedUser,
     error::{ApiError, Result},
     models::energy::{EnergyReading, EnergyReadingDb, EnergyReadingSubmission},
+    services::merkle::{MerkleTree, ReadingLeaf},
     AppState,
 };
 
@@ -259,4 +260,190 @@ pub async fn get_aggregated_readings(
     })?;
 
     Ok(Json(aggregated_data))
-}
\ No newline at end of file
+}
+
+/// Request to build a Merkle batch over one interval's readings
+#[derive(Debug, Deserialize)]
+pub struct ReadingBatchRequest {
+    pub interval_start: DateTime<Utc>,
+    pub interval_secs: u32,
+}
+
+/// Batch commitment to submit to the oracle's `commit_reading_batch`
+#[derive(Debug, Serialize)]
+pub struct ReadingBatchResponse {
+    pub merkle_root: String, // base58
+    pub reading_count: u32,
+    pub interval_start: i64,
+    pub interval_secs: u32,
+}
+
+/// Query parameters for a single reading's inclusion proof
+#[derive(Debug, Deserialize)]
+pub struct ReadingProofQuery {
+    pub reading_id: Uuid,
+    pub interval_start: DateTime<Utc>,
+    pub interval_secs: u32,
+}
+
+/// Inclusion proof in the on-chain reading representation
+#[derive(Debug, Serialize)]
+pub struct ReadingProofResponse {
+    pub meter_id: String,
+    pub energy_produced_wh: u64,
+    pub energy_consumed_wh: u64,
+    pub reading_timestamp: i64,
+    pub merkle_root: String,  // base58
+    pub proof: Vec<String>,   // base58, leaf to root
+}
+
+/// Weather interval used by the oracle program (`WEATHER_INTERVAL_SECS`)
+const WEATHER_INTERVAL_SECS: u32 = 900;
+
+#[derive(Debug, Deserialize)]
+pub struct WeatherObservationQuery {
+    pub interval_start: DateTime<Utc>,
+}
+
+/// Campus weather for one interval in the oracle's `submit_weather_observation` units
+#[derive(Debug, Serialize)]
+pub struct WeatherObservationResponse {
+    pub interval_start: i64,
+    pub irradiance_w_m2: u32,
+    pub temperature_centi_c: i32,
+    pub sample_count: usize,
+}
+
+/// Load an interval's readings in the canonical batch order
+async fn load_interval_readings(
+    state: &AppState,
+    interval_start: DateTime<Utc>,
+    interval_secs: u32,
+) -> Result<Vec<EnergyReading>> {
+    if interval_secs == 0 {
+        return Err(ApiError::BadRequest("Interval must be non-empty".to_string()));
+    }
+
+    let interval_end = interval_start + chrono::Duration::seconds(interval_secs as i64);
+
+    let readings = sqlx::query_as::<_, EnergyReadingDb>(
+        "SELECT id, meter_id, timestamp, energy_generated, energy_consumed, solar_irradiance, temperature, metadata, created_at FROM energy_readings WHERE timestamp >= $1 AND timestamp < $2 ORDER BY meter_id, timestamp, id"
+    )
+    .bind(interval_start)
+    .bind(interval_end)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch interval readings: {}", e);
+        ApiError::Database(e)
+    })?
+    .into_iter()
+    .map(|db_reading| db_reading.into())
+    .collect::<Vec<EnergyReading>>();
+
+    Ok(readings)
+}
+
+/// Build the Merkle commitment for an interval's readings
+/// POST /api/v1/meters/batches
+pub async fn build_reading_batch(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(payload): Json<ReadingBatchRequest>,
+) -> Result<Json<ReadingBatchResponse>> {
+    if !user.0.has_any_role(&["admin", "ami"]) {
+        return Err(ApiError::Authorization("Admin or AMI access required".to_string()));
+    }
+
+    let readings = load_interval_readings(&state, payload.interval_start, payload.interval_secs).await?;
+    let leaves = readings.iter().map(ReadingLeaf::from).collect::<Vec<_>>();
+    let tree = MerkleTree::new(&leaves);
+
+    let merkle_root = tree
+        .root()
+        .ok_or_else(|| ApiError::NotFound("No readings in interval".to_string()))?;
+
+    tracing::info!(
+        "Built reading batch for {} with {} readings",
+        payload.interval_start,
+        tree.leaf_count()
+    );
+
+    Ok(Json(ReadingBatchResponse {
+        merkle_root: bs58::encode(merkle_root).into_string(),
+        reading_count: tree.leaf_count() as u32,
+        interval_start: payload.interval_start.timestamp(),
+        interval_secs: payload.interval_secs,
+    }))
+}
+
+/// Get the inclusion proof for one reading in its interval batch
+/// GET /api/v1/meters/batches/proof
+pub async fn get_reading_proof(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Query(params): Query<ReadingProofQuery>,
+) -> Result<Json<ReadingProofResponse>> {
+    let readings = load_interval_readings(&state, params.interval_start, params.interval_secs).await?;
+
+    let index = readings
+        .iter()
+        .position(|r| r.id == Some(params.reading_id))
+        .ok_or_else(|| ApiError::NotFound("Reading not found in interval".to_string()))?;
+
+    let leaves = readings.iter().map(ReadingLeaf::from).collect::<Vec<_>>();
+    let tree = MerkleTree::new(&leaves);
+
+    let merkle_root = tree
+        .root()
+        .ok_or_else(|| ApiError::Internal("Empty reading batch".to_string()))?;
+    let proof = tree
+        .proof(index)
+        .ok_or_else(|| ApiError::Internal("Failed to build reading proof".to_string()))?;
+
+    let leaf = &leaves[index];
+    Ok(Json(ReadingProofResponse {
+        meter_id: leaf.meter_id.clone(),
+        energy_produced_wh: leaf.energy_produced_wh,
+        energy_consumed_wh: leaf.energy_consumed_wh,
+        reading_timestamp: leaf.reading_timestamp,
+        merkle_root: bs58::encode(merkle_root).into_string(),
+        proof: proof.iter().map(|node| bs58::encode(node).into_string()).collect(),
+    }))
+}
+
+/// Average the irradiance and temperature reported with an interval's readings
+/// GET /api/v1/meters/weather
+pub async fn get_weather_observation(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Query(params): Query<WeatherObservationQuery>,
+) -> Result<Json<WeatherObservationResponse>> {
+    if params.interval_start.timestamp() % WEATHER_INTERVAL_SECS as i64 != 0 {
+        return Err(ApiError::BadRequest(format!(
+            "Interval start must be aligned to {} seconds",
+            WEATHER_INTERVAL_SECS
+        )));
+    }
+
+    let readings = load_interval_readings(&state, params.interval_start, WEATHER_INTERVAL_SECS).await?;
+    let samples = readings
+        .iter()
+        .filter_map(|r| Some((r.solar_irradiance?, r.temperature?)))
+        .collect::<Vec<_>>();
+
+    if samples.is_empty() {
+        return Err(ApiError::NotFound("No weather data in interval".to_string()));
+    }
+
+    let count = samples.len() as f64;
+    let irradiance = samples.iter().map(|(i, _)| i).sum::<f64>() / count;
+    let temperature = samples.iter().map(|(_, t)| t).sum::<f64>() / count;
+
+    Ok(Json(WeatherObservationResponse {
+        interval_start: params.interval_start.timestamp(),
+        irradiance_w_m2: irradiance.round().max(0.0) as u32,
+        temperature_centi_c: (temperature * 100.0).round() as i32,
+        sample_count: samples.len(),
+    }))
+}
@@ -5,4 +5,7 @@ pub mod users;
 pub mod meters;
 pub mod trading;
 pub mod blockchain;
-pub mod analytics;
\ No newline at end of file
+pub mod analytics;
+pub mod disputes;
+pub mod referenda;
+pub mod protocol;
\ No newline at end of file
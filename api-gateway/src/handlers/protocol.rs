@@ -0,0 +1,60 @@
+use axum::{extract::State, response::Json};
+use redis::AsyncCommands;
+
+use crate::auth::middleware::AuthenticatedUser;
+use crate::error::{ApiError, Result};
+use crate::services::protocol_params::{ProtocolParams, CACHE_KEY};
+use crate::AppState;
+
+async fn cached_params(conn: &mut redis::aio::MultiplexedConnection) -> Result<Option<ProtocolParams>> {
+    let cached: Option<String> = conn.get(CACHE_KEY).await?;
+    cached
+        .map(|json| {
+            serde_json::from_str(&json)
+                .map_err(|e| ApiError::Internal(format!("Corrupt cached protocol params: {}", e)))
+        })
+        .transpose()
+}
+
+/// Get the cached protocol parameters
+/// GET /api/v1/protocol/params
+pub async fn get_protocol_params(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+) -> Result<Json<ProtocolParams>> {
+    let mut conn = state.redis.get_multiplexed_async_connection().await?;
+    let params = cached_params(&mut conn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Protocol parameters have not been published yet".to_string()))?;
+
+    Ok(Json(params))
+}
+
+/// Cache a new version of the protocol parameters, as announced by governance's
+/// `ProtocolParamsUpdated` event (admin only)
+/// POST /api/v1/protocol/params
+pub async fn update_protocol_params(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(payload): Json<ProtocolParams>,
+) -> Result<Json<ProtocolParams>> {
+    if !user.0.has_role("admin") {
+        return Err(ApiError::Authorization("Admin access required".to_string()));
+    }
+    payload.params.validate().map_err(ApiError::Validation)?;
+
+    let mut conn = state.redis.get_multiplexed_async_connection().await?;
+    let cached = cached_params(&mut conn).await?;
+    if !payload.supersedes(cached.as_ref()) {
+        // Late or repeated notification; keep the newer values already cached
+        return Ok(Json(cached.unwrap_or(payload)));
+    }
+
+    let json = serde_json::to_string(&payload)
+        .map_err(|e| ApiError::Internal(format!("Failed to encode protocol params: {}", e)))?;
+    conn.set::<_, _, ()>(CACHE_KEY, json).await?;
+
+    tracing::info!("Protocol parameters updated to version {}", payload.version);
+
+    Ok(Json(payload))
+}
@@ -0,0 +1,269 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::auth::middleware::AuthenticatedUser;
+use crate::error::{ApiError, Result};
+use crate::services::referendum::{ReferendumKind, ReferendumStatus, ReferendumTally};
+use crate::AppState;
+
+/// Same limit as the governance program's `MAX_DESCRIPTION_LEN`
+const MAX_QUESTION_LEN: usize = 256;
+
+/// Query parameters for listing ballots
+#[derive(Debug, Deserialize)]
+pub struct ReferendumQuery {
+    pub status: Option<ReferendumStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// A referendum opened on-chain, published as a ballot (admin only)
+#[derive(Debug, Deserialize)]
+pub struct PublishBallotRequest {
+    pub on_chain_id: i64,
+    pub kind: ReferendumKind,
+    pub question: String,
+    pub min_turnout: u64,
+    pub snapshot_at: DateTime<Utc>,
+    pub voting_ends_at: DateTime<Utc>,
+}
+
+/// Vote totals and status as mirrored from the governance program (admin only)
+#[derive(Debug, Deserialize)]
+pub struct UpdateResultsRequest {
+    pub status: ReferendumStatus,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub voter_count: u32,
+}
+
+/// What a holder votes on
+#[derive(Debug, Serialize)]
+pub struct BallotResponse {
+    pub id: Uuid,
+    /// Id of the governance `Referendum` account, which votes are cast against
+    pub on_chain_id: i64,
+    pub kind: ReferendumKind,
+    pub question: String,
+    pub status: ReferendumStatus,
+    /// Only tokens locked before this time carry weight
+    pub snapshot_at: DateTime<Utc>,
+    pub voting_ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResultsResponse {
+    pub id: Uuid,
+    pub on_chain_id: i64,
+    pub status: ReferendumStatus,
+    pub tally: ReferendumTally,
+    pub turnout: u64,
+    pub approval_bps: u16,
+    /// Whether the current tally passes; final once status leaves `voting`
+    pub passing: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ReferendumRow {
+    id: Uuid,
+    on_chain_id: i64,
+    kind: String,
+    question: String,
+    status: String,
+    yes_weight: i64,
+    no_weight: i64,
+    voter_count: i32,
+    min_turnout: i64,
+    snapshot_at: DateTime<Utc>,
+    voting_ends_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl ReferendumRow {
+    fn decode<T: serde::de::DeserializeOwned>(&self, value: &str) -> Result<T> {
+        serde_json::from_value(serde_json::Value::String(value.to_string()))
+            .map_err(|e| ApiError::Internal(format!("Corrupt referendum {}: {}", self.id, e)))
+    }
+
+    fn ballot(&self) -> Result<BallotResponse> {
+        Ok(BallotResponse {
+            id: self.id,
+            on_chain_id: self.on_chain_id,
+            kind: self.decode(&self.kind)?,
+            question: self.question.clone(),
+            status: self.decode(&self.status)?,
+            snapshot_at: self.snapshot_at,
+            voting_ends_at: self.voting_ends_at,
+        })
+    }
+
+    fn results(&self) -> Result<ResultsResponse> {
+        let tally = ReferendumTally {
+            yes_weight: self.yes_weight as u64,
+            no_weight: self.no_weight as u64,
+            voter_count: self.voter_count as u32,
+            min_turnout: self.min_turnout as u64,
+        };
+        Ok(ResultsResponse {
+            id: self.id,
+            on_chain_id: self.on_chain_id,
+            status: self.decode(&self.status)?,
+            turnout: tally.turnout(),
+            approval_bps: tally.approval_bps(),
+            passing: tally.passes(),
+            tally,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+const REFERENDUM_COLUMNS: &str =
+    "id, on_chain_id, kind, question, status, yes_weight, no_weight, voter_count, min_turnout, snapshot_at, voting_ends_at, updated_at";
+
+/// Token weights are u64 on-chain and BIGINT here
+fn to_db_weight(weight: u64) -> Result<i64> {
+    i64::try_from(weight).map_err(|_| ApiError::Validation("Token weight is too large".to_string()))
+}
+
+async fn load_referendum(state: &AppState, referendum_id: Uuid) -> Result<ReferendumRow> {
+    sqlx::query_as::<_, ReferendumRow>(&format!("SELECT {} FROM referenda WHERE id = $1", REFERENDUM_COLUMNS))
+        .bind(referendum_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch referendum: {}", e);
+            ApiError::Database(e)
+        })?
+        .ok_or_else(|| ApiError::NotFound("Referendum not found".to_string()))
+}
+
+/// List referendum ballots, newest first
+/// GET /api/v1/referenda
+pub async fn list_ballots(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Query(params): Query<ReferendumQuery>,
+) -> Result<Json<Vec<BallotResponse>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let rows = sqlx::query_as::<_, ReferendumRow>(&format!(
+        "SELECT {} FROM referenda WHERE ($1::text IS NULL OR status = $1) ORDER BY snapshot_at DESC LIMIT $2 OFFSET $3",
+        REFERENDUM_COLUMNS
+    ))
+    .bind(params.status.map(|status| status.as_str()))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch referenda: {}", e);
+        ApiError::Database(e)
+    })?;
+
+    let ballots = rows.iter().map(ReferendumRow::ballot).collect::<Result<Vec<_>>>()?;
+    Ok(Json(ballots))
+}
+
+/// Get one referendum's ballot
+/// GET /api/v1/referenda/:id
+pub async fn get_ballot(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Path(referendum_id): Path<Uuid>,
+) -> Result<Json<BallotResponse>> {
+    let row = load_referendum(&state, referendum_id).await?;
+    Ok(Json(row.ballot()?))
+}
+
+/// Get a referendum's token-weighted results
+/// GET /api/v1/referenda/:id/results
+pub async fn get_results(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Path(referendum_id): Path<Uuid>,
+) -> Result<Json<ResultsResponse>> {
+    let row = load_referendum(&state, referendum_id).await?;
+    Ok(Json(row.results()?))
+}
+
+/// Publish the ballot for a referendum opened on-chain (admin only)
+/// POST /api/v1/referenda
+pub async fn publish_ballot(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(payload): Json<PublishBallotRequest>,
+) -> Result<Json<BallotResponse>> {
+    if !user.0.has_role("admin") {
+        return Err(ApiError::Authorization("Admin access required".to_string()));
+    }
+    if payload.question.is_empty() || payload.question.len() > MAX_QUESTION_LEN {
+        return Err(ApiError::Validation(format!("Question must be 1 to {} bytes", MAX_QUESTION_LEN)));
+    }
+    if payload.voting_ends_at <= payload.snapshot_at {
+        return Err(ApiError::Validation("Voting must end after the snapshot".to_string()));
+    }
+
+    let row = sqlx::query_as::<_, ReferendumRow>(&format!(
+        "INSERT INTO referenda (id, on_chain_id, kind, question, status, yes_weight, no_weight, voter_count, min_turnout, snapshot_at, voting_ends_at, updated_at) VALUES ($1, $2, $3, $4, $5, 0, 0, 0, $6, $7, $8, $9) RETURNING {}",
+        REFERENDUM_COLUMNS
+    ))
+    .bind(Uuid::new_v4())
+    .bind(payload.on_chain_id)
+    .bind(payload.kind.as_str())
+    .bind(&payload.question)
+    .bind(ReferendumStatus::Voting.as_str())
+    .bind(to_db_weight(payload.min_turnout)?)
+    .bind(payload.snapshot_at)
+    .bind(payload.voting_ends_at)
+    .bind(Utc::now())
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store referendum: {}", e);
+        ApiError::Database(e)
+    })?;
+
+    tracing::info!("Referendum {} published as ballot {}", payload.on_chain_id, row.id);
+
+    Ok(Json(row.ballot()?))
+}
+
+/// Record vote totals and the outcome seen on-chain (admin only)
+/// PUT /api/v1/referenda/:id/results
+pub async fn update_results(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(referendum_id): Path<Uuid>,
+    Json(payload): Json<UpdateResultsRequest>,
+) -> Result<Json<ResultsResponse>> {
+    if !user.0.has_role("admin") {
+        return Err(ApiError::Authorization("Admin access required".to_string()));
+    }
+
+    let row = sqlx::query_as::<_, ReferendumRow>(&format!(
+        "UPDATE referenda SET status = $2, yes_weight = $3, no_weight = $4, voter_count = $5, updated_at = $6 WHERE id = $1 RETURNING {}",
+        REFERENDUM_COLUMNS
+    ))
+    .bind(referendum_id)
+    .bind(payload.status.as_str())
+    .bind(to_db_weight(payload.yes_weight)?)
+    .bind(to_db_weight(payload.no_weight)?)
+    .bind(payload.voter_count as i32)
+    .bind(Utc::now())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update referendum results: {}", e);
+        ApiError::Database(e)
+    })?
+    .ok_or_else(|| ApiError::NotFound("Referendum not found".to_string()))?;
+
+    Ok(Json(row.results()?))
+}
@@ -11,6 +11,7 @@ use crate::auth::middleware::AuthenticatedUser;
 use crate::database::schema::types::{OrderSide, OrderStatus, OrderType};
 use crate::error::{ApiError, Result};
 use crate::models::trading::{CreateOrderRequest, MarketData, OrderBook, TradingOrder, TradingOrderDb};
+use crate::services::tariff::{TariffPeriod, TariffSchedule};
 use crate::AppState;
 
 /// Query parameters for trading orders
@@ -29,6 +30,64 @@ pub struct CreateOrderResponse {
     pub status: OrderStatus,
     pub created_at: DateTime<Utc>,
     pub message: String,
+    /// Saving per kWh compared to the grid tariff, when one is in force
+    pub savings_per_kwh: Option<rust_decimal::Decimal>,
+}
+
+/// Tariff in force with the derived reference prices
+#[derive(Debug, Serialize)]
+pub struct TariffResponse {
+    pub schedule: TariffSchedule,
+    pub current_period: Option<TariffPeriod>,
+    pub min_price_per_kwh: Option<rust_decimal::Decimal>,
+    pub max_price_per_kwh: Option<rust_decimal::Decimal>,
+}
+
+impl TariffResponse {
+    fn new(schedule: TariffSchedule, at: DateTime<Utc>) -> Self {
+        let current_period = schedule.period_at(at).cloned();
+        let bands = schedule.price_bands();
+        TariffResponse {
+            schedule,
+            current_period,
+            min_price_per_kwh: bands.map(|(min, _)| min),
+            max_price_per_kwh: bands.map(|(_, max)| max),
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct TariffScheduleRow {
+    periods: String,
+    utc_offset_secs: i32,
+    effective_from: DateTime<Utc>,
+    effective_until: Option<DateTime<Utc>>,
+}
+
+/// Load the tariff schedule in force at `at`, if any
+async fn load_tariff_schedule(state: &AppState, at: DateTime<Utc>) -> Result<Option<TariffSchedule>> {
+    let row = sqlx::query_as::<_, TariffScheduleRow>(
+        "SELECT periods, utc_offset_secs, effective_from, effective_until FROM tariff_schedules WHERE effective_from <= $1 AND (effective_until IS NULL OR effective_until > $1) ORDER BY effective_from DESC LIMIT 1"
+    )
+    .bind(at)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to load tariff schedule: {}", e);
+        ApiError::Database(e)
+    })?;
+
+    row.map(|row| {
+        let periods = serde_json::from_str(&row.periods)
+            .map_err(|e| ApiError::Internal(format!("Corrupt tariff periods: {}", e)))?;
+        Ok(TariffSchedule {
+            periods,
+            utc_offset_secs: row.utc_offset_secs,
+            effective_from: row.effective_from,
+            effective_until: row.effective_until,
+        })
+    })
+    .transpose()
 }
 
 /// Create a new trading order
@@ -49,9 +108,34 @@ pub async fn create_order(
         return Err(ApiError::BadRequest("Price per kWh must be positive".to_string()));
     }
 
+    // Only users with an attested university ID may trade, so one person cannot trade from several wallets
+    let identity_attested_at = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+        "SELECT identity_attested_at FROM users WHERE id = $1 AND is_active = true"
+    )
+    .bind(user.0.sub)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(ApiError::Database)?
+    .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+    if identity_attested_at.is_none() {
+        return Err(ApiError::Authorization("Attest your identity before placing orders".to_string()));
+    }
+
     // Create trading order
     let order_id = Uuid::new_v4();
     let now = Utc::now();
+    let tariff = load_tariff_schedule(&state, now).await?;
+
+    // Outside the tariff bands one side would be better off trading with the grid
+    if let Some((min_price, max_price)) = tariff.as_ref().and_then(TariffSchedule::price_bands) {
+        if payload.price_per_kwh < min_price || payload.price_per_kwh > max_price {
+            return Err(ApiError::BadRequest(format!(
+                "Price per kWh must be between {} and {}",
+                min_price, max_price
+            )));
+        }
+    }
     let expires_at = payload.expiry_time.unwrap_or_else(|| now + chrono::Duration::days(1));
 
     // Determine order side based on user role/permissions (simplified logic)
@@ -60,6 +144,9 @@ pub async fn create_order(
     } else {
         OrderSide::Sell
     };
+    let savings_per_kwh = tariff
+        .as_ref()
+        .and_then(|t| t.savings_per_kwh(&order_side, payload.price_per_kwh, now));
 
     // Convert Decimal to BigDecimal for database storage
     let energy_amount_bd = {
@@ -107,6 +194,7 @@ pub async fn create_order(
         status: OrderStatus::Pending,
         created_at: now,
         message: "Order created successfully".to_string(),
+        savings_per_kwh,
     }))
 }
 
@@ -189,6 +277,9 @@ pub async fn get_market_data(
     let epoch_start = DateTime::from_timestamp(current_epoch as i64 * 3600, 0).unwrap();
     let epoch_end = epoch_start + chrono::Duration::hours(1);
 
+    let tariff = load_tariff_schedule(&state, now).await?;
+    let grid_period = tariff.as_ref().and_then(|t| t.period_at(now));
+
     // For now, return basic market data structure
     // In Phase 4, this will include real order book and trade data
     let market_data = MarketData {
@@ -201,6 +292,8 @@ pub async fn get_market_data(
             buy_orders: vec![],
         },
         recent_trades: vec![],
+        grid_import_price: grid_period.map(|p| p.import_price),
+        grid_feed_in_price: grid_period.map(|p| p.feed_in_price),
     };
 
     Ok(Json(market_data))
@@ -232,4 +325,58 @@ pub async fn get_trading_stats(
     };
 
     Ok(Json(trading_stats))
-}
\ No newline at end of file
+}
+
+/// Get the grid tariff in force
+/// GET /api/v1/trading/tariff
+pub async fn get_tariff(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+) -> Result<Json<TariffResponse>> {
+    let now = Utc::now();
+    let schedule = load_tariff_schedule(&state, now)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("No tariff schedule in force".to_string()))?;
+
+    Ok(Json(TariffResponse::new(schedule, now)))
+}
+
+/// Publish a new grid tariff schedule (admin only)
+/// PUT /api/v1/trading/tariff
+pub async fn update_tariff(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(payload): Json<TariffSchedule>,
+) -> Result<Json<TariffResponse>> {
+    if !user.0.has_role("admin") {
+        return Err(ApiError::Authorization("Admin access required".to_string()));
+    }
+
+    payload.validate().map_err(ApiError::Validation)?;
+
+    let periods = serde_json::to_string(&payload.periods)
+        .map_err(|e| ApiError::Internal(format!("Failed to encode tariff periods: {}", e)))?;
+
+    sqlx::query(
+        "INSERT INTO tariff_schedules (id, periods, utc_offset_secs, effective_from, effective_until, created_by, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7)"
+    )
+    .bind(Uuid::new_v4())
+    .bind(periods)
+    .bind(payload.utc_offset_secs)
+    .bind(payload.effective_from)
+    .bind(payload.effective_until)
+    .bind(user.0.sub)
+    .bind(Utc::now())
+    .execute(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store tariff schedule: {}", e);
+        ApiError::Database(e)
+    })?;
+
+    // TODO: In Phase 4, submit oracle update_tariff_schedule so trading price bands follow
+
+    tracing::info!("Tariff schedule effective {} published by {}", payload.effective_from, user.0.sub);
+
+    Ok(Json(TariffResponse::new(payload, Utc::now())))
+}
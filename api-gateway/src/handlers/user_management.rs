@@ -11,6 +11,7 @@ use crate::auth::{SecureAuthResponse, Claims, UserInfo, SecureUserInfo};
 use crate::auth::middleware::AuthenticatedUser;
 use crate::auth::password::PasswordService;
 use crate::error::{ApiError, Result};
+use crate::services::identity::identity_hash;
 use crate::AppState;
 
 /// Enhanced user registration request with additional validation
@@ -54,6 +55,21 @@ pub struct WalletAddressRequest {
     pub verify_ownership: Option<bool>,
 }
 
+/// Campus identity attestation request
+#[derive(Debug, Deserialize, Validate)]
+pub struct IdentityAttestationRequest {
+    #[validate(length(min = 1, max = 32))]
+    pub university_id: String,
+}
+
+/// Identity hash to be attested on-chain for the user's wallet
+#[derive(Debug, Serialize)]
+pub struct IdentityAttestationResponse {
+    pub wallet_address: String,
+    pub identity_hash: String,
+    pub attested_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Admin user update request
 #[derive(Debug, Deserialize, Validate, Serialize)]
 pub struct AdminUserUpdateRequest {
@@ -232,7 +248,7 @@ pub async fn update_wallet_address(
     State(state): State<AppState>,
     user: AuthenticatedUser,
     Json(request): Json<WalletAddressRequest>,
-) -> Result<Json<UserInfo>> {
+) -> Result<Json<crate::handlers::auth::ProfileResponse>> {
     // Validate request
     request.validate()
         .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
@@ -256,9 +272,10 @@ pub async fn update_wallet_address(
         return Err(ApiError::BadRequest("Wallet address is already in use".to_string()));
     }
 
-    // Update wallet address
+    // Update wallet address; the identity attestation was for the old wallet and must be redone
     let result = sqlx::query(
-        "UPDATE users SET wallet_address = $1, updated_at = NOW() WHERE id = $2 AND is_active = true"
+        "UPDATE users SET wallet_address = $1, identity_hash = NULL, identity_attested_at = NULL, updated_at = NOW()
+         WHERE id = $2 AND is_active = true"
     )
     .bind(&request.wallet_address)
     .bind(user.0.sub)
@@ -293,7 +310,8 @@ pub async fn remove_wallet_address(
 ) -> Result<StatusCode> {
     // Update wallet address to null
     let result = sqlx::query(
-        "UPDATE users SET wallet_address = NULL, blockchain_registered = false, updated_at = NOW() 
+        "UPDATE users SET wallet_address = NULL, blockchain_registered = false,
+         identity_hash = NULL, identity_attested_at = NULL, updated_at = NOW()
          WHERE id = $1 AND is_active = true"
     )
     .bind(user.0.sub)
@@ -318,6 +336,70 @@ pub async fn remove_wallet_address(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Link the current user's wallet to their university ID, completing account setup.
+///
+/// Each university ID can back only one account, so one person cannot register
+/// several wallets to trade with.
+pub async fn attest_identity(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<IdentityAttestationRequest>,
+) -> Result<Json<IdentityAttestationResponse>> {
+    request.validate()
+        .map_err(|e| ApiError::BadRequest(format!("Validation error: {}", e)))?;
+
+    let wallet_address = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT wallet_address FROM users WHERE id = $1 AND is_active = true"
+    )
+    .bind(user.0.sub)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| ApiError::Internal(format!("Database error: {}", e)))?
+    .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?
+    .ok_or_else(|| ApiError::BadRequest("Link a wallet address before attesting your identity".to_string()))?;
+
+    let identity_hash = identity_hash(&state.config.identity_salt, &request.university_id);
+
+    // The unique index on identity_hash rejects a university ID already linked elsewhere
+    let attested_at = sqlx::query_scalar::<_, chrono::DateTime<chrono::Utc>>(
+        "UPDATE users SET identity_hash = $1, identity_attested_at = NOW(), updated_at = NOW()
+         WHERE id = $2 AND is_active = true
+         RETURNING identity_attested_at"
+    )
+    .bind(identity_hash.as_slice())
+    .bind(user.0.sub)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+            ApiError::Conflict("University ID is already linked to another account".to_string())
+        }
+        e => ApiError::Internal(format!("Failed to attest identity: {}", e)),
+    })?;
+
+    // TODO: In Phase 4, submit registry attest_user signed by the gateway's IdentityAttestor key
+
+    let identity_hash = bs58::encode(identity_hash).into_string();
+
+    let _ = log_user_activity(
+        &state.db,
+        user.0.sub,
+        "identity_attested".to_string(),
+        Some(serde_json::json!({
+            "wallet_address": wallet_address,
+            "identity_hash": identity_hash
+        })),
+        None,
+        None,
+    ).await;
+
+    Ok(Json(IdentityAttestationResponse {
+        wallet_address,
+        identity_hash,
+        attested_at,
+    }))
+}
+
 /// Admin: Update any user (requires admin role)
 pub async fn admin_update_user(
     State(state): State<AppState>,
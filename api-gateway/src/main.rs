@@ -18,7 +18,7 @@ mod error;
 mod auth;
 
 use config::Config;
-use handlers::{health, auth as auth_handlers, user_management, blockchain, analytics, trading, meters};
+use handlers::{health, auth as auth_handlers, user_management, blockchain, analytics, trading, meters, disputes, referenda, protocol};
 use auth::{jwt::JwtService, jwt::ApiKeyService};
 
 /// Application state shared across handlers
@@ -103,6 +103,7 @@ async fn main() -> Result<()> {
         .nest("/user", Router::new()
             .route("/wallet", post(user_management::update_wallet_address))
             .route("/wallet", axum::routing::delete(user_management::remove_wallet_address))
+            .route("/identity", post(user_management::attest_identity))
             .route("/activity", get(user_management::get_user_activity))
             .layer(from_fn_with_state(
                 app_state.clone(),
@@ -147,6 +148,7 @@ async fn main() -> Result<()> {
             .route("/orders", get(trading::get_user_orders))
             .route("/market", get(trading::get_market_data))
             .route("/stats", get(trading::get_trading_stats))
+            .route("/tariff", get(trading::get_tariff).put(trading::update_tariff))
             .layer(from_fn_with_state(
                 app_state.clone(),
                 auth::middleware::auth_middleware,
@@ -159,6 +161,39 @@ async fn main() -> Result<()> {
             .route("/readings", get(meters::get_energy_readings))
             .route("/readings/:id", get(meters::get_energy_reading_by_id))
             .route("/aggregated", get(meters::get_aggregated_readings))
+            .route("/batches", post(meters::build_reading_batch))
+            .route("/batches/proof", get(meters::get_reading_proof))
+            .route("/weather", get(meters::get_weather_observation))
+            .layer(from_fn_with_state(
+                app_state.clone(),
+                auth::middleware::auth_middleware,
+            ))
+        )
+        
+        // Dispute routes (authenticated users; outcome updates are admin only)
+        .nest("/disputes", Router::new()
+            .route("/", post(disputes::open_dispute).get(disputes::list_disputes))
+            .route("/:id", get(disputes::get_dispute).put(disputes::update_dispute))
+            .layer(from_fn_with_state(
+                app_state.clone(),
+                auth::middleware::auth_middleware,
+            ))
+        )
+        
+        // Referendum ballots and results (authenticated users; publishing is admin only)
+        .nest("/referenda", Router::new()
+            .route("/", get(referenda::list_ballots).post(referenda::publish_ballot))
+            .route("/:id", get(referenda::get_ballot))
+            .route("/:id/results", get(referenda::get_results).put(referenda::update_results))
+            .layer(from_fn_with_state(
+                app_state.clone(),
+                auth::middleware::auth_middleware,
+            ))
+        )
+        
+        // Cached governance protocol parameters (authenticated users; updates are admin only)
+        .nest("/protocol", Router::new()
+            .route("/params", get(protocol::get_protocol_params).post(protocol::update_protocol_params))
             .layer(from_fn_with_state(
                 app_state.clone(),
                 auth::middleware::auth_middleware,
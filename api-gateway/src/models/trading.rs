@@ -71,6 +71,9 @@ pub struct MarketData {
     pub status: String,
     pub order_book: OrderBook,
     pub recent_trades: Vec<TradeExecution>,
+    /// Utility reference prices for the current time-of-use period
+    pub grid_import_price: Option<rust_decimal::Decimal>,
+    pub grid_feed_in_price: Option<rust_decimal::Decimal>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,4 +35,15 @@ pub struct UserProfile {
 pub struct UserBalances {
     pub grid_tokens: rust_decimal::Decimal,
     pub pending_trades: rust_decimal::Decimal,
-}
\ No newline at end of file
+}
+/// Meters another user has delegated to this user on-chain, mirrored from the
+/// registry's `Delegation` accounts
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DelegatedMeter {
+    pub owner_wallet: String,
+    /// `None` when the delegation covers all of the owner's meters
+    pub meter_id: Option<String>,
+    /// Any of `meter_status`, `trading`, `view_data`
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,114 @@
+//! Trade and reading disputes, mirroring the governance program's `Dispute` account.
+//!
+//! A dispute names its remedy up front: a refund on a trade record, or a signed correction
+//! to a meter's totals. REC validators vote on it on-chain, and the remedy is applied only
+//! if they uphold it.
+
+use serde::{Deserialize, Serialize};
+
+/// Same limits as the governance program's `MAX_EVIDENCE_URIS` and `MAX_EVIDENCE_URI_LEN`
+pub const MAX_EVIDENCE_URIS: usize = 4;
+pub const MAX_EVIDENCE_URI_LEN: usize = 128;
+
+/// What is disputed and the remedy requested, in on-chain units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DisputeSubject {
+    /// Seller under-delivered; refund the buyer part of the trade value
+    Trade { trade_record: String, refund_amount: u64 },
+    /// Wrong meter reading; adjust the meter's totals in Wh
+    Reading {
+        meter: String,
+        reading_timestamp: i64,
+        generation_delta: i64,
+        consumption_delta: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DisputeStatus {
+    Voting,
+    Upheld,
+    Rejected,
+}
+
+impl DisputeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeStatus::Voting => "voting",
+            DisputeStatus::Upheld => "upheld",
+            DisputeStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl DisputeSubject {
+    /// Apply the same rules as the governance program so `open_dispute` cannot fail
+    pub fn validate(&self, evidence_uris: &[String]) -> Result<(), String> {
+        if evidence_uris.is_empty() || evidence_uris.len() > MAX_EVIDENCE_URIS {
+            return Err(format!("Dispute needs between 1 and {} evidence URIs", MAX_EVIDENCE_URIS));
+        }
+        if evidence_uris.iter().any(|uri| uri.is_empty() || uri.len() > MAX_EVIDENCE_URI_LEN) {
+            return Err(format!("Evidence URIs must be 1 to {} bytes", MAX_EVIDENCE_URI_LEN));
+        }
+
+        let (account, has_remedy) = match self {
+            DisputeSubject::Trade { trade_record, refund_amount } => (trade_record, *refund_amount > 0),
+            DisputeSubject::Reading { meter, generation_delta, consumption_delta, .. } => {
+                (meter, *generation_delta != 0 || *consumption_delta != 0)
+            }
+        };
+        if !is_account_address(account) {
+            return Err("Disputed account must be a base58 Solana address".to_string());
+        }
+        if !has_remedy {
+            return Err("Dispute must request a non-zero refund or correction".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn is_account_address(address: &str) -> bool {
+    bs58::decode(address).into_vec().map(|bytes| bytes.len() == 32).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRADE_RECORD: &str = "UbU6TWh6YP4kYQuj8t7xiNg65NdEQF9kfAKa4aS85iS";
+
+    fn evidence() -> Vec<String> {
+        vec!["https://files.campus.local/disputes/delivery-log.csv".to_string()]
+    }
+
+    #[test]
+    fn test_valid_disputes() {
+        let trade = DisputeSubject::Trade { trade_record: TRADE_RECORD.to_string(), refund_amount: 500 };
+        assert!(trade.validate(&evidence()).is_ok());
+
+        let reading = DisputeSubject::Reading {
+            meter: TRADE_RECORD.to_string(),
+            reading_timestamp: 1_750_000_000,
+            generation_delta: -1_200,
+            consumption_delta: 0,
+        };
+        assert!(reading.validate(&evidence()).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_disputes_the_program_would_reject() {
+        let trade = DisputeSubject::Trade { trade_record: TRADE_RECORD.to_string(), refund_amount: 500 };
+        assert!(trade.validate(&[]).is_err());
+        assert!(trade.validate(&vec![evidence()[0].clone(); MAX_EVIDENCE_URIS + 1]).is_err());
+        assert!(trade.validate(&["x".repeat(MAX_EVIDENCE_URI_LEN + 1)]).is_err());
+
+        let no_refund = DisputeSubject::Trade { trade_record: TRADE_RECORD.to_string(), refund_amount: 0 };
+        assert!(no_refund.validate(&evidence()).is_err());
+
+        let bad_address = DisputeSubject::Trade { trade_record: "not-an-address".to_string(), refund_amount: 500 };
+        assert!(bad_address.validate(&evidence()).is_err());
+    }
+}
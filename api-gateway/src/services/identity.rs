@@ -0,0 +1,38 @@
+//! Campus identity attestation, matching the registry program's `attest_user`.
+//!
+//! The on-chain identity hash is `sha256(salt || normalized university ID)`. The salt is a
+//! gateway secret so the small university ID space cannot be brute-forced from chain data,
+//! while the same person still always hashes the same way and can only attest one wallet.
+
+use sha2::{Digest, Sha256};
+
+pub type IdentityHash = [u8; 32];
+
+/// University IDs are compared case-insensitively and without surrounding whitespace
+fn normalize(university_id: &str) -> String {
+    university_id.trim().to_ascii_uppercase()
+}
+
+pub fn identity_hash(salt: &str, university_id: &str) -> IdentityHash {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(normalize(university_id).as_bytes());
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_person_hashes_the_same() {
+        assert_eq!(identity_hash("salt", "6410001234"), identity_hash("salt", " 6410001234 "));
+        assert_eq!(identity_hash("salt", "st-0042"), identity_hash("salt", "ST-0042"));
+        assert_ne!(identity_hash("salt", "ST-0042"), identity_hash("salt", "ST-0043"));
+    }
+
+    #[test]
+    fn test_salt_changes_hash() {
+        assert_ne!(identity_hash("campus-a", "ST-0042"), identity_hash("campus-b", "ST-0042"));
+    }
+}
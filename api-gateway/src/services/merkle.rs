@@ -0,0 +1,163 @@
+//! Merkle trees over meter readings, matching the oracle program's batch commitments.
+//!
+//! Leaves are `sha256(0x00 || len(meter_id) as u32 LE || meter_id || produced_wh LE || consumed_wh LE || timestamp LE)`
+//! and interior nodes are `sha256(0x01 || min(a, b) || max(a, b))`, so proofs need no position bits.
+
+use sha2::{Digest, Sha256};
+
+use crate::models::energy::EnergyReading;
+
+pub type Hash = [u8; 32];
+
+/// A reading in the on-chain representation (energy in Wh, unix timestamp)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadingLeaf {
+    pub meter_id: String,
+    pub energy_produced_wh: u64,
+    pub energy_consumed_wh: u64,
+    pub reading_timestamp: i64,
+}
+
+impl ReadingLeaf {
+    pub fn hash(&self) -> Hash {
+        let mut hasher = Sha256::new();
+        hasher.update([0u8]);
+        hasher.update((self.meter_id.len() as u32).to_le_bytes());
+        hasher.update(self.meter_id.as_bytes());
+        hasher.update(self.energy_produced_wh.to_le_bytes());
+        hasher.update(self.energy_consumed_wh.to_le_bytes());
+        hasher.update(self.reading_timestamp.to_le_bytes());
+        hasher.finalize().into()
+    }
+}
+
+impl From<&EnergyReading> for ReadingLeaf {
+    fn from(reading: &EnergyReading) -> Self {
+        ReadingLeaf {
+            meter_id: reading.meter_id.clone(),
+            energy_produced_wh: kwh_to_wh(reading.energy_generated),
+            energy_consumed_wh: kwh_to_wh(reading.energy_consumed),
+            reading_timestamp: reading.timestamp.timestamp(),
+        }
+    }
+}
+
+fn kwh_to_wh(kwh: f64) -> u64 {
+    (kwh * 1000.0).round().max(0.0) as u64
+}
+
+fn hash_pair(a: &Hash, b: &Hash) -> Hash {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Binary Merkle tree; an odd node at the end of a level is promoted unchanged
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: &[ReadingLeaf]) -> Self {
+        let mut levels = vec![leaves.iter().map(ReadingLeaf::hash).collect::<Vec<_>>()];
+
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Root of the tree, or `None` when there are no leaves
+    pub fn root(&self) -> Option<Hash> {
+        self.levels.last().and_then(|level| level.first().copied())
+    }
+
+    /// Sibling hashes from leaf `index` up to the root
+    pub fn proof(&self, index: usize) -> Option<Vec<Hash>> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+
+        let mut proof = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+/// Verify a proof the same way the oracle program does
+pub fn verify_proof(proof: &[Hash], root: &Hash, leaf: &Hash) -> bool {
+    let computed = proof.iter().fold(*leaf, |node, sibling| hash_pair(&node, sibling));
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(i: u64) -> ReadingLeaf {
+        ReadingLeaf {
+            meter_id: format!("METER_{:03}", i),
+            energy_produced_wh: 1000 + i,
+            energy_consumed_wh: 500 + i,
+            reading_timestamp: 1_700_000_000 + i as i64,
+        }
+    }
+
+    #[test]
+    fn test_every_leaf_proves_against_root() {
+        for count in 1..=9 {
+            let leaves: Vec<_> = (0..count).map(leaf).collect();
+            let tree = MerkleTree::new(&leaves);
+            let root = tree.root().unwrap();
+
+            for (i, l) in leaves.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(verify_proof(&proof, &root, &l.hash()), "leaf {} of {}", i, count);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_reading_fails() {
+        let leaves: Vec<_> = (0..4).map(leaf).collect();
+        let tree = MerkleTree::new(&leaves);
+        let root = tree.root().unwrap();
+        let proof = tree.proof(2).unwrap();
+
+        let mut forged = leaves[2].clone();
+        forged.energy_produced_wh += 1;
+        assert!(!verify_proof(&proof, &root, &forged.hash()));
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let tree = MerkleTree::new(&[]);
+        assert_eq!(tree.leaf_count(), 0);
+        assert!(tree.root().is_none());
+        assert!(tree.proof(0).is_none());
+    }
+}
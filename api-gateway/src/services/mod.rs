@@ -1,2 +1,9 @@
 // Business logic services
-// Authentication, blockchain client, trading engine, etc.
\ No newline at end of file
+// Authentication, blockchain client, trading engine, etc.
+
+pub mod dispute;
+pub mod identity;
+pub mod merkle;
+pub mod protocol_params;
+pub mod referendum;
+pub mod tariff;
@@ -0,0 +1,112 @@
+//! Protocol parameters, mirroring the governance program's `ProtocolParams` account.
+//!
+//! Governance bumps the version on every change and emits `ProtocolParamsUpdated`; the
+//! event listener forwards it here so the gateway serves the current values from cache
+//! instead of reading the account on every request.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Redis key holding the cached parameters as JSON
+pub const CACHE_KEY: &str = "protocol_params";
+
+/// Same fields and units as the program's `ParamValues`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamValues {
+    pub market_fee_bps: u16,
+    pub min_rec_validators: u8,
+    pub oracle_reporter: String,
+    pub epoch_duration_secs: i64,
+    pub min_clearing_interval_secs: i64,
+    pub max_staleness_secs: i64,
+    pub min_price_per_kwh: u64,
+    /// 0 means no ceiling
+    pub max_price_per_kwh: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtocolParams {
+    pub version: u64,
+    pub params: ParamValues,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ParamValues {
+    /// Same rules as `ParamValues::is_valid` in the governance program
+    pub fn validate(&self) -> Result<(), String> {
+        if self.market_fee_bps > 10_000 {
+            return Err("Market fee must be at most 10000 bps".to_string());
+        }
+        if self.min_rec_validators == 0 {
+            return Err("At least one REC validator must be required".to_string());
+        }
+        if !bs58::decode(&self.oracle_reporter).into_vec().map(|bytes| bytes.len() == 32).unwrap_or(false) {
+            return Err("Oracle reporter must be a base58 Solana address".to_string());
+        }
+        if self.epoch_duration_secs <= 0 || self.min_clearing_interval_secs < 0 || self.max_staleness_secs <= 0 {
+            return Err("Epoch, clearing and staleness windows must be positive".to_string());
+        }
+        if self.max_price_per_kwh != 0 && self.min_price_per_kwh > self.max_price_per_kwh {
+            return Err("Minimum price cannot exceed the maximum price".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl ProtocolParams {
+    /// Whether these values should replace the cached ones; notifications can arrive
+    /// late or twice, so only a newer version wins
+    pub fn supersedes(&self, cached: Option<&ProtocolParams>) -> bool {
+        cached.is_none_or(|cached| self.version > cached.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(version: u64) -> ProtocolParams {
+        ProtocolParams {
+            version,
+            params: ParamValues {
+                market_fee_bps: 25,
+                min_rec_validators: 2,
+                oracle_reporter: "UbU6TWh6YP4kYQuj8t7xiNg65NdEQF9kfAKa4aS85iS".to_string(),
+                epoch_duration_secs: 3_600,
+                min_clearing_interval_secs: 300,
+                max_staleness_secs: 1_800,
+                min_price_per_kwh: 0,
+                max_price_per_kwh: 0,
+            },
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_only_newer_versions_replace_the_cache() {
+        assert!(params(1).supersedes(None));
+        assert!(params(3).supersedes(Some(&params(2))));
+        assert!(!params(2).supersedes(Some(&params(2))), "duplicate notification");
+        assert!(!params(1).supersedes(Some(&params(2))), "late notification");
+    }
+
+    #[test]
+    fn test_rejects_params_the_program_would_reject() {
+        assert!(params(1).params.validate().is_ok());
+
+        let mut fee = params(1).params;
+        fee.market_fee_bps = 10_001;
+        assert!(fee.validate().is_err());
+
+        let mut prices = params(1).params;
+        prices.min_price_per_kwh = 5_000;
+        prices.max_price_per_kwh = 4_000;
+        assert!(prices.validate().is_err());
+        prices.max_price_per_kwh = 0;
+        assert!(prices.validate().is_ok(), "no ceiling");
+
+        let mut reporter = params(1).params;
+        reporter.oracle_reporter = "gateway".to_string();
+        assert!(reporter.validate().is_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! Community referenda, mirroring the governance program's `Referendum` account.
+//!
+//! Voting weight is the energy tokens a holder locked before the referendum opened,
+//! counted in token base units. Advisory referenda only record the result; binding ones
+//! execute a governance action when they pass.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferendumKind {
+    Advisory,
+    Binding,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferendumStatus {
+    Voting,
+    Passed,
+    Rejected,
+    Executed,
+    Cancelled,
+}
+
+impl ReferendumKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferendumKind::Advisory => "advisory",
+            ReferendumKind::Binding => "binding",
+        }
+    }
+}
+
+impl ReferendumStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferendumStatus::Voting => "voting",
+            ReferendumStatus::Passed => "passed",
+            ReferendumStatus::Rejected => "rejected",
+            ReferendumStatus::Executed => "executed",
+            ReferendumStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Token-weighted vote totals for one referendum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferendumTally {
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub voter_count: u32,
+    pub min_turnout: u64,
+}
+
+impl ReferendumTally {
+    pub fn turnout(&self) -> u64 {
+        self.yes_weight.saturating_add(self.no_weight)
+    }
+
+    /// Same rule as `Referendum::passed`: enough tokens voted and more for than against
+    pub fn passes(&self) -> bool {
+        self.turnout() >= self.min_turnout && self.yes_weight > self.no_weight
+    }
+
+    /// Share of the voted weight in favour, in basis points
+    pub fn approval_bps(&self) -> u16 {
+        match self.turnout() {
+            0 => 0,
+            turnout => (self.yes_weight as u128 * 10_000 / turnout as u128) as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(yes_weight: u64, no_weight: u64) -> ReferendumTally {
+        ReferendumTally { yes_weight, no_weight, voter_count: 3, min_turnout: 1_000 }
+    }
+
+    #[test]
+    fn test_needs_turnout_and_majority() {
+        assert!(tally(700, 400).passes());
+        assert!(!tally(600, 300).passes(), "below turnout");
+        assert!(!tally(500, 500).passes(), "a tie is not a majority");
+    }
+
+    #[test]
+    fn test_approval_share() {
+        assert_eq!(tally(750, 250).approval_bps(), 7_500);
+        assert_eq!(tally(0, 0).approval_bps(), 0);
+    }
+}
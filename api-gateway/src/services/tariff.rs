@@ -0,0 +1,148 @@
+//! Grid time-of-use tariff, mirroring the oracle program's `TariffSchedule` account.
+//!
+//! The utility tariff is the reference price for P2P trading: the lowest feed-in price
+//! and highest import price bound order prices, and the difference between an order's
+//! price and the tariff is what the user saves compared to trading with the grid.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::database::schema::types::OrderSide;
+
+/// Same limit as the oracle program's `MAX_TARIFF_PERIODS`
+pub const MAX_TARIFF_PERIODS: usize = 8;
+
+/// Utility prices for one time-of-use period, in tokens per kWh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TariffPeriod {
+    /// Local hour, inclusive
+    pub start_hour: u8,
+    /// Local hour, exclusive
+    pub end_hour: u8,
+    pub import_price: Decimal,
+    pub feed_in_price: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TariffSchedule {
+    pub periods: Vec<TariffPeriod>,
+    pub utc_offset_secs: i32,
+    pub effective_from: DateTime<Utc>,
+    pub effective_until: Option<DateTime<Utc>>,
+}
+
+impl TariffSchedule {
+    /// Apply the same rules as the oracle program so the on-chain update cannot fail
+    pub fn validate(&self) -> Result<(), String> {
+        let periods = &self.periods;
+        if periods.is_empty() || periods.len() > MAX_TARIFF_PERIODS {
+            return Err(format!("Tariff must have between 1 and {} periods", MAX_TARIFF_PERIODS));
+        }
+        if periods[0].start_hour != 0 || periods[periods.len() - 1].end_hour != 24 {
+            return Err("Tariff periods must cover 00:00 to 24:00".to_string());
+        }
+        for (i, period) in periods.iter().enumerate() {
+            if period.start_hour >= period.end_hour {
+                return Err(format!("Period {} ends before it starts", i));
+            }
+            if i > 0 && periods[i - 1].end_hour != period.start_hour {
+                return Err(format!("Period {} does not start where period {} ends", i, i - 1));
+            }
+            if period.feed_in_price < Decimal::ZERO || period.feed_in_price > period.import_price {
+                return Err(format!("Period {} feed-in price must be between 0 and the import price", i));
+            }
+        }
+        if !(-14 * 3600..=14 * 3600).contains(&self.utc_offset_secs) {
+            return Err("UTC offset out of range".to_string());
+        }
+        if matches!(self.effective_until, Some(until) if until <= self.effective_from) {
+            return Err("Tariff must end after it takes effect".to_string());
+        }
+        Ok(())
+    }
+
+    /// Period in force at `at`, or `None` outside the effective dates
+    pub fn period_at(&self, at: DateTime<Utc>) -> Option<&TariffPeriod> {
+        if at < self.effective_from || matches!(self.effective_until, Some(until) if at >= until) {
+            return None;
+        }
+
+        let local_hour = (at.timestamp() + self.utc_offset_secs as i64).rem_euclid(86_400) / 3600;
+        self.periods
+            .iter()
+            .find(|p| local_hour >= p.start_hour as i64 && local_hour < p.end_hour as i64)
+    }
+
+    /// Lowest feed-in price and highest import price across all periods
+    pub fn price_bands(&self) -> Option<(Decimal, Decimal)> {
+        let min_price = self.periods.iter().map(|p| p.feed_in_price).min()?;
+        let max_price = self.periods.iter().map(|p| p.import_price).max()?;
+        Some((min_price, max_price))
+    }
+
+    /// Per-kWh saving of a P2P price over the grid at `at`.
+    ///
+    /// Buyers save the import price minus what they pay; sellers earn their price
+    /// minus the feed-in credit they would otherwise get.
+    pub fn savings_per_kwh(&self, side: &OrderSide, price_per_kwh: Decimal, at: DateTime<Utc>) -> Option<Decimal> {
+        let period = self.period_at(at)?;
+        Some(match side {
+            OrderSide::Buy => period.import_price - price_per_kwh,
+            OrderSide::Sell => price_per_kwh - period.feed_in_price,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule() -> TariffSchedule {
+        TariffSchedule {
+            periods: vec![
+                TariffPeriod { start_hour: 0, end_hour: 9, import_price: Decimal::new(26, 1), feed_in_price: Decimal::new(22, 1) },
+                TariffPeriod { start_hour: 9, end_hour: 22, import_price: Decimal::new(56, 1), feed_in_price: Decimal::new(22, 1) },
+                TariffPeriod { start_hour: 22, end_hour: 24, import_price: Decimal::new(26, 1), feed_in_price: Decimal::new(22, 1) },
+            ],
+            utc_offset_secs: 7 * 3600,
+            effective_from: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            effective_until: None,
+        }
+    }
+
+    #[test]
+    fn test_period_lookup_uses_local_time() {
+        let tariff = schedule();
+        assert!(tariff.validate().is_ok());
+
+        // 03:00 UTC is 10:00 local, inside the peak period
+        let peak = Utc.with_ymd_and_hms(2025, 6, 1, 3, 0, 0).unwrap();
+        assert_eq!(tariff.period_at(peak).unwrap().import_price, Decimal::new(56, 1));
+
+        // 16:00 UTC is 23:00 local, off-peak
+        let off_peak = Utc.with_ymd_and_hms(2025, 6, 1, 16, 0, 0).unwrap();
+        assert_eq!(tariff.period_at(off_peak).unwrap().import_price, Decimal::new(26, 1));
+
+        let before = Utc.with_ymd_and_hms(2024, 12, 31, 0, 0, 0).unwrap();
+        assert!(tariff.period_at(before).is_none());
+    }
+
+    #[test]
+    fn test_savings_and_bands() {
+        let tariff = schedule();
+        let peak = Utc.with_ymd_and_hms(2025, 6, 1, 3, 0, 0).unwrap();
+
+        assert_eq!(tariff.price_bands(), Some((Decimal::new(22, 1), Decimal::new(56, 1))));
+        assert_eq!(tariff.savings_per_kwh(&OrderSide::Buy, Decimal::new(40, 1), peak), Some(Decimal::new(16, 1)));
+        assert_eq!(tariff.savings_per_kwh(&OrderSide::Sell, Decimal::new(40, 1), peak), Some(Decimal::new(18, 1)));
+    }
+
+    #[test]
+    fn test_rejects_gaps() {
+        let mut tariff = schedule();
+        tariff.periods[1].start_hour = 10;
+        assert!(tariff.validate().is_err());
+    }
+}
@@ -147,6 +147,31 @@ initialize_poa_governance() {
     fi
 }
 
+# Function to make governance the upgrade authority of every program
+govern_program_upgrades() {
+    cd /workspaces/p2p || {
+        log "ERROR: Cannot change to workspace root"
+        return 1
+    }
+    
+    # [deployment] upgrade_authority in programs.config.toml; governance unless set to "keypair"
+    local upgrade_authority
+    upgrade_authority=$(sed -n 's/^upgrade_authority *= *"\([a-z]*\)".*/\1/p' programs.config.toml 2>/dev/null)
+    if [ "${upgrade_authority:-governance}" = "keypair" ]; then
+        log "Upgrade authority kept by the deployer (upgrade_authority = \"keypair\")"
+        return 0
+    fi
+    
+    log "Handing program upgrade authority to governance..."
+    if anchor run govern-upgrades 2>&1 | tee -a "$DEPLOY_LOG_FILE"; then
+        log "Programs are now upgradeable only through governance proposals"
+        return 0
+    fi
+    
+    log "ERROR: Failed to hand upgrade authority to governance"
+    return 1
+}
+
 # Function to airdrop SOL to key accounts
 setup_test_accounts() {
     log "Setting up test accounts with SOL..."
@@ -209,6 +234,9 @@ main() {
             # Initialize PoA governance
             initialize_poa_governance
             
+            # Governance becomes the upgrade authority, once PoA is set up
+            govern_program_upgrades
+            
             # Mark deployment as successful
             echo "SUCCESS" > "$DEPLOY_STATUS_FILE"
             log "=== Smart Contract Deployment Completed Successfully ==="
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, Burn};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer, Burn};
+use governance::{PauseConfig, PauseScope};
+use oracle::{ReadingAssessment, ReadingBatch};
+use registry::{MeterAccount, MeterStatus};
 
 declare_id!("J61eiwojt9zA1TP6t9M9wEDmiDek4QNEY2HiAfunHs7");
 
@@ -64,6 +67,101 @@ pub mod energy_token {
         Ok(())
     }
     
+    /// Mint tokens for a meter's generation by proving the reading is in a committed oracle batch
+    ///
+    /// The mint authority must be the `token_info` PDA. Anyone may submit the proof;
+    /// each meter reading can be minted once, however many batches include it, and a
+    /// meter's readings are minted in timestamp order. 1 token = 1 kWh.
+    pub fn mint_from_reading_proof(
+        ctx: Context<MintFromReadingProof>,
+        meter_id: String,
+        energy_produced: u64,
+        energy_consumed: u64,
+        reading_timestamp: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let reading_batch = &ctx.accounts.reading_batch;
+        let meter_account = &ctx.accounts.meter_account;
+        
+        require!(meter_account.status == MeterStatus::Active, ErrorCode::InvalidMeter);
+        require!(energy_produced > 0, ErrorCode::NothingToMint);
+        require!(reading_batch.covers(reading_timestamp)?, ErrorCode::ReadingOutsideBatch);
+        
+        let leaf = oracle::reading_leaf(&meter_id, energy_produced, energy_consumed, reading_timestamp);
+        require!(
+            oracle::verify_reading_proof(&proof, reading_batch.merkle_root, leaf),
+            ErrorCode::InvalidReadingProof
+        );
+        
+        // Batched readings skip per-reading checks at commit time, so enforce them here,
+        // over the period since the meter's last minted reading
+        let mint_cursor = &mut ctx.accounts.mint_cursor;
+        let interval_secs = mint_cursor.reading_interval(reading_timestamp)?;
+        let assessment = oracle::assess_reading(
+            meter_account,
+            energy_produced,
+            energy_consumed,
+            interval_secs,
+        )?;
+        require!(
+            assessment == ReadingAssessment::Plausible,
+            ErrorCode::ImplausibleReading
+        );
+        require!(
+            oracle::solar_deviation(
+                meter_account,
+                energy_produced,
+                interval_secs,
+                &ctx.accounts.weather_observation,
+            )?
+            .is_none(),
+            ErrorCode::ImplausibleReading
+        );
+        
+        // Readings are in Wh; scale to token base units
+        let amount = 10u64
+            .checked_pow(ctx.accounts.mint.decimals as u32)
+            .and_then(|unit| energy_produced.checked_mul(unit))
+            .map(|scaled| scaled / 1_000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        mint_cursor.last_reading_timestamp = reading_timestamp;
+        
+        let minted_reading = &mut ctx.accounts.minted_reading;
+        minted_reading.reading_batch = reading_batch.key();
+        minted_reading.leaf = leaf;
+        minted_reading.amount = amount;
+        minted_reading.minted_at = Clock::get()?.unix_timestamp;
+        
+        let bump = ctx.bumps.token_info;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"token_info", &[bump]]];
+        
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.token_info.to_account_info(),
+        };
+        
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        token::mint_to(cpi_ctx, amount)?;
+        
+        let token_info = &mut ctx.accounts.token_info;
+        token_info.total_supply = token_info.total_supply.saturating_add(amount);
+        
+        emit!(ReadingTokensMinted {
+            meter_id,
+            owner: meter_account.owner,
+            reading_batch: reading_batch.key(),
+            energy_produced,
+            amount,
+            timestamp: minted_reading.minted_at,
+        });
+        
+        Ok(())
+    }
+    
     /// Burn energy tokens (for energy consumption)
     pub fn burn_tokens(
         ctx: Context<BurnTokens>,
@@ -135,6 +233,72 @@ pub struct TransferTokens<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+#[instruction(meter_id: String, energy_produced: u64, energy_consumed: u64, reading_timestamp: i64)]
+pub struct MintFromReadingProof<'info> {
+    #[account(mut, seeds = [b"token_info"], bump, has_one = mint)]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Minting) @ ErrorCode::MintingPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    pub reading_batch: Account<'info, ReadingBatch>,
+    
+    #[account(
+        seeds = [b"meter", meter_id.as_bytes()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    /// CHECK: Oracle weather for the reading's interval; checked by `oracle::solar_deviation`
+    #[account(
+        seeds = [b"weather".as_ref(), &oracle::weather_interval_start(reading_timestamp).to_le_bytes()],
+        bump,
+        seeds::program = oracle::ID
+    )]
+    pub weather_observation: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MintedReading::INIT_SPACE,
+        seeds = [b"minted_reading", meter_id.as_bytes(), &reading_timestamp.to_le_bytes()],
+        bump
+    )]
+    pub minted_reading: Account<'info, MintedReading>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MeterMintCursor::INIT_SPACE,
+        seeds = [b"mint_cursor", meter_id.as_bytes()],
+        bump
+    )]
+    pub mint_cursor: Account<'info, MeterMintCursor>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = owner_token_account.owner == meter_account.owner @ ErrorCode::InvalidMeter
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct BurnTokens<'info> {
     #[account(mut)]
@@ -161,6 +325,50 @@ pub struct TokenInfo {
     pub created_at: i64,
 }
 
+/// Receipt preventing the same meter reading from being minted twice, even when
+/// it was committed in more than one batch
+#[account]
+#[derive(InitSpace)]
+pub struct MintedReading {
+    pub reading_batch: Pubkey,
+    pub leaf: [u8; 32],
+    pub amount: u64,
+    pub minted_at: i64,
+}
+
+/// Timestamp of a meter's last minted reading, which bounds the next reading's interval
+#[account]
+#[derive(InitSpace)]
+pub struct MeterMintCursor {
+    pub last_reading_timestamp: i64,
+}
+
+impl MeterMintCursor {
+    /// Seconds covered by a reading, measured from the last minted one. Skipped readings
+    /// cannot be minted later, so minted energy never exceeds capacity over elapsed time.
+    pub fn reading_interval(&self, reading_timestamp: i64) -> Result<i64> {
+        if self.last_reading_timestamp == 0 {
+            return Ok(oracle::DEFAULT_READING_INTERVAL_SECS);
+        }
+        require!(
+            reading_timestamp > self.last_reading_timestamp,
+            ErrorCode::ReadingOutOfOrder
+        );
+        Ok(reading_timestamp - self.last_reading_timestamp)
+    }
+}
+
+// Events
+#[event]
+pub struct ReadingTokensMinted {
+    pub meter_id: String,
+    pub owner: Pubkey,
+    pub reading_batch: Pubkey,
+    pub energy_produced: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
 // Errors
 #[error_code]
 pub enum ErrorCode {
@@ -170,4 +378,18 @@ pub enum ErrorCode {
     InvalidMeter,
     #[msg("Insufficient token balance")]
     InsufficientBalance,
+    #[msg("Reading has no generation to mint")]
+    NothingToMint,
+    #[msg("Reading timestamp is outside the batch interval")]
+    ReadingOutsideBatch,
+    #[msg("Invalid reading inclusion proof")]
+    InvalidReadingProof,
+    #[msg("Reading exceeds the meter's rated capacity or deviates from the recorded weather")]
+    ImplausibleReading,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Minting is paused")]
+    MintingPaused,
+    #[msg("Reading is not newer than the meter's last minted reading")]
+    ReadingOutOfOrder,
 }
\ No newline at end of file
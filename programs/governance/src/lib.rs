@@ -1,108 +1,177 @@
 use anchor_lang::prelude::*;
+#[allow(deprecated)] // Re-exported by Anchor; the replacement crate is not a dependency here
+use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::pubkey;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 declare_id!("8SD7rh3fMtLsS9ciVKvyhVUkHimq7TEfgfE4AqUi2Lxt");
 
-/// University authority public key (hardcoded for demo)
-pub const UNIVERSITY_AUTHORITY_PUBKEY: Pubkey = Pubkey::new_from_array([1u8; 32]);
+/// Maximum keys in the university authority multisig
+pub const MAX_AUTHORITY_SIGNERS: usize = 7;
+
+/// Programs governed through proposals. Governance is the leaf of the program
+/// dependency graph, so it calls them by raw CPI rather than through their crates.
+pub const TRADING_PROGRAM_ID: Pubkey = pubkey!("UbU6TWh6YP4kYQuj8t7xiNg65NdEQF9kfAKa4aS85iS");
+pub const ORACLE_PROGRAM_ID: Pubkey = pubkey!("7sA8No5jojLboTzQQTU3fiAL8kGAjTzPgXtaMEYNKPEC");
+pub const REGISTRY_PROGRAM_ID: Pubkey = pubkey!("BkZ9cBB3FFGXxJPw5JTJAbVAQYaDBeoQKoSL7b5u5Snv");
+
+/// Bounds on the proposal voting window
+pub const MIN_VOTING_PERIOD_SECS: i64 = 60;
+pub const MAX_VOTING_PERIOD_SECS: i64 = 30 * 24 * 3600;
+
+/// Bounds on the delay before a sensitive action can execute
+pub const MIN_TIMELOCK_DELAY_SECS: i64 = 60;
+pub const MAX_TIMELOCK_DELAY_SECS: i64 = 14 * 24 * 3600;
+
+/// Maximum proposal description length
+pub const MAX_DESCRIPTION_LEN: usize = 256;
+
+/// Space reserved for a REC validator's department name
+pub const MAX_VALIDATOR_NAME_LEN: usize = 64;
+
+/// Validators below this reliability are deactivated automatically, unless changed by the authority
+pub const DEFAULT_MIN_RELIABILITY_BPS: u16 = 8_000;
+
+/// Expected attestations needed before reliability can deactivate a validator
+pub const MIN_RELIABILITY_SAMPLE: u64 = 20;
+
+/// Time a validator must stay inactive before withdrawing stake, leaving room to
+/// report and slash overturned certifications
+pub const STAKE_UNBONDING_SECS: i64 = 7 * 24 * 3600;
+
+/// Deployed program hashes kept per program; older entries roll off (events keep them all)
+pub const MAX_DEPLOY_HISTORY: usize = 16;
+
+/// Evidence a dispute can cite, e.g. links to meter photos or delivery logs
+pub const MAX_EVIDENCE_URIS: usize = 4;
+pub const MAX_EVIDENCE_URI_LEN: usize = 128;
 
 #[program]
 pub mod governance {
     use super::*;
     
-    /// Simple initialization for testing
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let poa_config = &mut ctx.accounts.poa_config;
-        poa_config.university_authority = ctx.accounts.authority.key();
-        poa_config.authorized_rec_validators = Vec::new();
-        poa_config.min_rec_validators = 1;
-        poa_config.emergency_paused = false;
-        poa_config.created_at = Clock::get()?.unix_timestamp;
+    /// Create the pause flags read by the other programs (university authority only)
+    pub fn initialize_pause_config(ctx: Context<InitializePauseConfig>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        
+        let pause_config = &mut ctx.accounts.pause_config;
+        pause_config.guardian = guardian;
+        pause_config.paused_scopes = 0;
+        pause_config.updated_at = Clock::get()?.unix_timestamp;
         
-        msg!("Governance program initialized");
         Ok(())
     }
-
-    /// Emergency pause functionality
-    pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
-        let poa_config = &mut ctx.accounts.poa_config;
+    
+    /// Replace the pause guardian (university authority only)
+    pub fn set_pause_guardian(ctx: Context<SetPauseGuardian>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
         
-        require!(
-            ctx.accounts.authority.key() == poa_config.university_authority,
-            ErrorCode::UnauthorizedAuthority
-        );
+        let pause_config = &mut ctx.accounts.pause_config;
+        let old_guardian = pause_config.guardian;
+        pause_config.guardian = guardian;
+        
+        emit!(PauseGuardianUpdated {
+            old_guardian,
+            new_guardian: guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Pause one scope, or everything with `PauseScope::Global`
+    ///
+    /// The guardian can pause alone so on-call can stop damage quickly; only the
+    /// university authority multisig can unpause.
+    pub fn emergency_pause(ctx: Context<EmergencyPause>, scope: PauseScope) -> Result<()> {
+        let authority = &ctx.accounts.authority;
+        if authority.key() != ctx.accounts.pause_config.guardian {
+            ctx.accounts.poa_config.require_authority(authority, ctx.remaining_accounts)?;
+        }
         
-        poa_config.emergency_paused = true;
+        let now = Clock::get()?.unix_timestamp;
+        let pause_config = &mut ctx.accounts.pause_config;
+        pause_config.paused_scopes |= scope.bit();
+        pause_config.updated_at = now;
         
         emit!(EmergencyPauseActivated {
-            authority: ctx.accounts.authority.key(),
-            timestamp: Clock::get()?.unix_timestamp,
+            authority: authority.key(),
+            scope,
+            timestamp: now,
         });
         
         msg!("Emergency pause activated");
         Ok(())
     }
 
-    /// Emergency unpause functionality
-    pub fn emergency_unpause(ctx: Context<EmergencyUnpause>) -> Result<()> {
-        let poa_config = &mut ctx.accounts.poa_config;
-        
-        require!(
-            ctx.accounts.authority.key() == poa_config.university_authority,
-            ErrorCode::UnauthorizedAuthority
-        );
+    /// Lift the pause on one scope (university authority only)
+    ///
+    /// Unpausing `Global` does not lift narrower scopes that were paused separately.
+    pub fn emergency_unpause(ctx: Context<EmergencyUnpause>, scope: PauseScope) -> Result<()> {
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
         
-        poa_config.emergency_paused = false;
+        let now = Clock::get()?.unix_timestamp;
+        let pause_config = &mut ctx.accounts.pause_config;
+        pause_config.paused_scopes &= !scope.bit();
+        pause_config.updated_at = now;
         
         emit!(EmergencyPauseDeactivated {
             authority: ctx.accounts.authority.key(),
-            timestamp: Clock::get()?.unix_timestamp,
+            scope,
+            timestamp: now,
         });
         
         msg!("Emergency pause deactivated");
         Ok(())
     }
     
-    /// Initialize PoA governance with REC validators
-    pub fn initialize_poa_with_rec(ctx: Context<InitializePoAWithRec>) -> Result<()> {
+    /// Initialize PoA governance with REC validators and the starting protocol parameters
+    ///
+    /// Only the program's upgrade authority can call this, so the deployer cannot be
+    /// front-run into someone else's authority and validators.
+    pub fn initialize_poa_with_rec(ctx: Context<InitializePoAWithRec>, params: ParamValues) -> Result<()> {
+        require!(params.is_valid(), ErrorCode::InvalidProtocolParams);
         let poa_config = &mut ctx.accounts.poa_config;
         
-        // Verify university authority
-        require!(
-            ctx.accounts.university_authority.key() == UNIVERSITY_AUTHORITY_PUBKEY,
-            ErrorCode::UnauthorizedAuthority
-        );
+        // The initializer starts as a 1-of-1 authority and rotates to the full multisig
+        poa_config.set_sole_authority(ctx.accounts.university_authority.key());
+        let now = Clock::get()?.unix_timestamp;
         
-        poa_config.university_authority = ctx.accounts.university_authority.key();
-        poa_config.authorized_rec_validators = vec![
-            // University departments as REC certification authority
-            RecValidatorInfo {
-                pubkey: ctx.accounts.sustainability_validator.key(),
-                authority_name: "University Sustainability Office".to_string(),
-                certification_authority: true,
-                active: true,
-                added_at: Clock::get()?.unix_timestamp,
-            },
-            RecValidatorInfo {
-                pubkey: ctx.accounts.engineering_validator.key(),
-                authority_name: "University Engineering Department".to_string(),
-                certification_authority: true,
-                active: true,
-                added_at: Clock::get()?.unix_timestamp,
-            },
-            RecValidatorInfo {
-                pubkey: ctx.accounts.facilities_validator.key(),
-                authority_name: "University Facilities Management".to_string(),
-                certification_authority: true,
-                active: true,
-                added_at: Clock::get()?.unix_timestamp,
-            },
-        ];
-        poa_config.min_rec_validators = 2; // Minimum for REC consensus
-        poa_config.created_at = Clock::get()?.unix_timestamp;
+        // University departments as REC certification authority
+        ctx.accounts.sustainability_rec_validator.set_inner(RecValidator::new(
+            ctx.accounts.sustainability_validator.key(),
+            "University Sustainability Office".to_string(),
+            now,
+        ));
+        ctx.accounts.engineering_rec_validator.set_inner(RecValidator::new(
+            ctx.accounts.engineering_validator.key(),
+            "University Engineering Department".to_string(),
+            now,
+        ));
+        ctx.accounts.facilities_rec_validator.set_inner(RecValidator::new(
+            ctx.accounts.facilities_validator.key(),
+            "University Facilities Management".to_string(),
+            now,
+        ));
+        poa_config.rec_validator_count = 3;
+        poa_config.active_rec_validator_count = 3;
+        poa_config.set_min_validators(params.min_rec_validators)?;
+        poa_config.min_reliability_bps = DEFAULT_MIN_RELIABILITY_BPS;
+        poa_config.created_at = now;
+        
+        ctx.accounts.protocol_params.set_inner(ProtocolParams {
+            version: 1,
+            params,
+            updated_at: now,
+        });
         
         emit!(PoAInitialized {
             authority: ctx.accounts.university_authority.key(),
-            validator_count: poa_config.authorized_rec_validators.len() as u8,
+            validator_count: poa_config.rec_validator_count,
             min_validators: poa_config.min_rec_validators,
             timestamp: Clock::get()?.unix_timestamp,
         });
@@ -110,103 +179,251 @@ pub mod governance {
         Ok(())
     }
     
-    /// Add a new authorized REC validator
+    /// Add a REC validator while governance is bootstrapping (university authority only)
+    ///
+    /// Once proposals are set up, validators are only added through
+    /// `ProposalAction::AddRecValidator`.
     pub fn add_authorized_rec_validator(
         ctx: Context<AddRecValidator>,
         validator_pubkey: Pubkey,
         department: String,
     ) -> Result<()> {
+        require!(ctx.accounts.proposal_config.data_is_empty(), ErrorCode::BootstrapEnded);
         let poa_config = &mut ctx.accounts.poa_config;
+        poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        require!(department.len() <= MAX_VALIDATOR_NAME_LEN, ErrorCode::InvalidValidatorName);
+        
+        poa_config.register_validator()?;
+        ctx.accounts.rec_validator.set_inner(RecValidator::new(
+            validator_pubkey,
+            department,
+            Clock::get()?.unix_timestamp,
+        ));
+        
+        emit!(RecValidatorAdded {
+            validator: validator_pubkey,
+            authority: ctx.accounts.university_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Count a certification by `validator` (oracle or registry only, via CPI when a
+    /// validator reviews a quarantined reading or activates a meter)
+    pub fn record_attestation(ctx: Context<RecordAttestation>, _validator: Pubkey) -> Result<()> {
+        apply_attestation(
+            &mut ctx.accounts.poa_config,
+            &mut ctx.accounts.rec_validator,
+            AttestationOutcome::Certified,
+        )
+    }
+    
+    /// Open the stake account for a REC validator
+    pub fn open_validator_stake(ctx: Context<OpenValidatorStake>) -> Result<()> {
+        let stake = &mut ctx.accounts.validator_stake;
+        stake.validator = ctx.accounts.validator.key();
+        stake.amount = 0;
+        stake.slashed_amount = 0;
+        stake.slashed_certifications = 0;
+        stake.updated_at = Clock::get()?.unix_timestamp;
+        
+        Ok(())
+    }
+    
+    /// Add lamports to a validator's stake
+    pub fn deposit_stake(ctx: Context<DepositStake>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.validator.to_account_info(),
+                    to: ctx.accounts.validator_stake.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
         
-        // Only university authority can add validators
+        let stake = &mut ctx.accounts.validator_stake;
+        stake.amount = stake.amount.checked_add(amount).ok_or(ErrorCode::InvalidStakeAmount)?;
+        stake.updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(ValidatorStakeDeposited {
+            validator: stake.validator,
+            amount,
+            total_stake: stake.amount,
+            timestamp: stake.updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    /// Withdraw stake once the validator has been inactive for `STAKE_UNBONDING_SECS` and
+    /// every overturned certification has been slashed, so stake cannot leave ahead of
+    /// the slash for a false certification
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, amount: u64) -> Result<()> {
+        let validator = ctx.accounts.validator.key();
+        let rec_validator = &ctx.accounts.rec_validator;
+        let now = Clock::get()?.unix_timestamp;
+        require!(!rec_validator.active, ErrorCode::ValidatorStillActive);
         require!(
-            ctx.accounts.university_authority.key() == poa_config.university_authority,
-            ErrorCode::UnauthorizedAuthority
+            now >= rec_validator.deactivated_at.saturating_add(STAKE_UNBONDING_SECS),
+            ErrorCode::StakeUnbonding
         );
         
+        let stake = &mut ctx.accounts.validator_stake;
         require!(
-            !poa_config.authorized_rec_validators.iter().any(|v| v.pubkey == validator_pubkey),
-            ErrorCode::ValidatorAlreadyAuthorized
+            rec_validator.overturned_attestations <= stake.slashed_certifications,
+            ErrorCode::UnslashedFault
         );
+        require!(amount > 0 && amount <= stake.amount, ErrorCode::InvalidStakeAmount);
+        
+        stake.amount -= amount;
+        stake.updated_at = now;
+        pay_out_lamports(&stake.to_account_info(), &ctx.accounts.validator.to_account_info(), amount)?;
+        
+        emit!(ValidatorStakeWithdrawn {
+            validator,
+            amount,
+            total_stake: stake.amount,
+            timestamp: stake.updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    /// Slash a validator's stake for one proven false certification (university authority only)
+    ///
+    /// Each certification overturned by an upheld dispute can be slashed once; the
+    /// lamports go to the dispute treasury.
+    pub fn slash_validator_stake(ctx: Context<SlashValidatorStake>, amount: u64) -> Result<()> {
+        let poa_config = &ctx.accounts.poa_config;
+        poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
         
+        let stake = &mut ctx.accounts.validator_stake;
         require!(
-            poa_config.authorized_rec_validators.len() < PoAConfig::MAX_REC_VALIDATORS,
-            ErrorCode::MaxValidatorsExceeded
+            ctx.accounts.rec_validator.overturned_attestations > stake.slashed_certifications,
+            ErrorCode::NoSlashableFault
         );
+        require!(amount > 0 && amount <= stake.amount, ErrorCode::InvalidStakeAmount);
         
-        let new_validator = RecValidatorInfo {
-            pubkey: validator_pubkey,
-            authority_name: department,
-            certification_authority: true,
-            active: true,
-            added_at: Clock::get()?.unix_timestamp,
-        };
-        
-        poa_config.authorized_rec_validators.push(new_validator);
+        stake.amount -= amount;
+        stake.slashed_amount += amount;
+        stake.slashed_certifications += 1;
+        stake.updated_at = Clock::get()?.unix_timestamp;
+        pay_out_lamports(&stake.to_account_info(), &ctx.accounts.treasury.to_account_info(), amount)?;
         
-        emit!(RecValidatorAdded {
-            validator: validator_pubkey,
+        emit!(ValidatorSlashed {
+            validator: stake.validator,
+            amount,
+            recipient: ctx.accounts.treasury.key(),
             authority: ctx.accounts.university_authority.key(),
+            timestamp: stake.updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    /// Close a removed validator's tombstone once its stake has been withdrawn or slashed
+    /// (validator only); the rent goes back to the validator
+    pub fn close_rec_validator(ctx: Context<CloseRecValidator>) -> Result<()> {
+        let stake_info = &ctx.accounts.validator_stake;
+        if !stake_info.data_is_empty() {
+            let stake = ValidatorStake::try_deserialize(&mut &stake_info.try_borrow_data()?[..])?;
+            require!(stake.amount == 0, ErrorCode::StakeNotSettled);
+        }
+        
+        emit!(RecValidatorClosed {
+            validator: ctx.accounts.validator.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
     
-    /// Remove an authorized REC validator
-    pub fn remove_authorized_rec_validator(
-        ctx: Context<RemoveRecValidator>,
-        validator_pubkey: Pubkey,
+    /// Propose a new M-of-N university authority (current authority multisig only).
+    ///
+    /// Takes effect once the new members accept, so a mistyped key cannot lock governance out.
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_signers: Vec<Pubkey>,
+        new_threshold: u8,
     ) -> Result<()> {
         let poa_config = &mut ctx.accounts.poa_config;
         
-        require!(
-            ctx.accounts.university_authority.key() == poa_config.university_authority,
-            ErrorCode::UnauthorizedAuthority
-        );
+        poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        poa_config.propose_authority(new_signers.clone(), new_threshold)?;
         
-        require!(
-            poa_config.authorized_rec_validators.len() > poa_config.min_rec_validators as usize,
-            ErrorCode::InsufficientValidators
-        );
+        emit!(AuthorityRotationProposed {
+            proposed_by: ctx.accounts.university_authority.key(),
+            signers: new_signers,
+            threshold: new_threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
-        let validator_exists = poa_config.authorized_rec_validators.iter().any(|v| v.pubkey == validator_pubkey);
-        require!(validator_exists, ErrorCode::ValidatorNotFound);
+        Ok(())
+    }
+    
+    /// Accept a proposed authority rotation (threshold of the proposed members)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let poa_config = &mut ctx.accounts.poa_config;
         
-        poa_config.authorized_rec_validators.retain(|v| v.pubkey != validator_pubkey);
+        poa_config.accept_authority(&ctx.accounts.new_authority, ctx.remaining_accounts)?;
         
-        emit!(RecValidatorRemoved {
-            validator: validator_pubkey,
-            authority: ctx.accounts.university_authority.key(),
+        emit!(AuthorityRotated {
+            signers: poa_config.authority_signers.clone(),
+            threshold: poa_config.authority_threshold,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
     
-    /// Update minimum required REC validators
-    pub fn update_min_rec_validators(
-        ctx: Context<UpdateMinValidators>,
-        new_min: u8,
+    /// Set up validator proposals with the given voting window and timelock delay
+    /// (university authority only)
+    pub fn initialize_proposals(
+        ctx: Context<InitializeProposals>,
+        voting_period_secs: i64,
+        timelock_delay_secs: i64,
     ) -> Result<()> {
-        let poa_config = &mut ctx.accounts.poa_config;
-        
         require!(
-            ctx.accounts.university_authority.key() == poa_config.university_authority,
-            ErrorCode::UnauthorizedAuthority
+            (MIN_VOTING_PERIOD_SECS..=MAX_VOTING_PERIOD_SECS).contains(&voting_period_secs),
+            ErrorCode::InvalidVotingPeriod
+        );
+        require!(
+            (MIN_TIMELOCK_DELAY_SECS..=MAX_TIMELOCK_DELAY_SECS).contains(&timelock_delay_secs),
+            ErrorCode::InvalidTimelockDelay
         );
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
         
+        let proposal_config = &mut ctx.accounts.proposal_config;
+        proposal_config.proposal_count = 0;
+        proposal_config.voting_period_secs = voting_period_secs;
+        proposal_config.timelock_delay_secs = timelock_delay_secs;
+        
+        Ok(())
+    }
+    
+    /// Change the delay applied to newly queued sensitive actions (university authority only)
+    pub fn update_timelock_delay(
+        ctx: Context<UpdateTimelockDelay>,
+        timelock_delay_secs: i64,
+    ) -> Result<()> {
         require!(
-            new_min > 0 && new_min <= poa_config.authorized_rec_validators.len() as u8,
-            ErrorCode::InvalidMinValidators
+            (MIN_TIMELOCK_DELAY_SECS..=MAX_TIMELOCK_DELAY_SECS).contains(&timelock_delay_secs),
+            ErrorCode::InvalidTimelockDelay
         );
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
         
-        let old_min = poa_config.min_rec_validators;
-        poa_config.min_rec_validators = new_min;
+        let proposal_config = &mut ctx.accounts.proposal_config;
+        let old_delay_secs = proposal_config.timelock_delay_secs;
+        proposal_config.timelock_delay_secs = timelock_delay_secs;
         
-        emit!(MinValidatorsUpdated {
-            old_min,
-            new_min,
+        emit!(TimelockDelayUpdated {
+            old_delay_secs,
+            new_delay_secs: timelock_delay_secs,
             authority: ctx.accounts.university_authority.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
@@ -214,142 +431,1041 @@ pub mod governance {
         Ok(())
     }
     
-    /// Deactivate a REC validator (suspend without removing)
-    pub fn deactivate_rec_validator(
-        ctx: Context<DeactivateRecValidator>,
-        validator_pubkey: Pubkey,
+    /// Propose a governance action (active REC validators only)
+    ///
+    /// The quorum is fixed at a majority of the active validators when the proposal is
+    /// created.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        action: ProposalAction,
+        description: String,
     ) -> Result<()> {
-        let poa_config = &mut ctx.accounts.poa_config;
-        
         require!(
-            ctx.accounts.university_authority.key() == poa_config.university_authority,
-            ErrorCode::UnauthorizedAuthority
+            description.len() <= MAX_DESCRIPTION_LEN && action.is_valid(),
+            ErrorCode::InvalidProposal
         );
         
-        let active_count = poa_config.authorized_rec_validators.iter().filter(|v| v.active).count();
-        require!(
-            active_count > poa_config.min_rec_validators as usize,
-            ErrorCode::InsufficientActiveValidators
-        );
+        let now = Clock::get()?.unix_timestamp;
+        let proposal_config = &mut ctx.accounts.proposal_config;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = proposal_config.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.action = action;
+        proposal.description = description;
+        proposal.status = ProposalStatus::Voting;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.quorum = ctx.accounts.poa_config.majority_quorum();
+        proposal.created_at = now;
+        proposal.voting_ends_at = now + proposal_config.voting_period_secs;
+        proposal.eta = 0;
+        proposal.cancel_votes = 0;
+        proposal.executed_at = 0;
         
-        let validator = poa_config.authorized_rec_validators.iter_mut()
-            .find(|v| v.pubkey == validator_pubkey)
-            .ok_or(ErrorCode::ValidatorNotFound)?;
+        proposal_config.proposal_count += 1;
         
-        require!(validator.active, ErrorCode::ValidatorAlreadyInactive);
+        emit!(ProposalCreated {
+            proposal_id: proposal.id,
+            proposer: proposal.proposer,
+            voting_ends_at: proposal.voting_ends_at,
+            timestamp: now,
+        });
         
-        validator.active = false;
+        Ok(())
+    }
+    
+    /// Vote on a proposal within its voting window (active REC validators, once each)
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
         
-        emit!(RecValidatorDeactivated {
-            validator: validator_pubkey,
+        require!(proposal.status == ProposalStatus::Voting, ErrorCode::ProposalNotActive);
+        require!(now < proposal.voting_ends_at, ErrorCode::VotingClosed);
+        
+        if approve {
+            proposal.yes_votes += 1;
+        } else {
+            proposal.no_votes += 1;
+        }
+        
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.approve = approve;
+        vote_record.voted_at = now;
+        
+        emit!(VoteCast {
+            proposal_id: proposal.id,
+            voter: vote_record.voter,
+            approve,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Close voting and schedule a passed proposal (permissionless)
+    ///
+    /// Sensitive actions get an eta `timelock_delay_secs` out, giving users time to react;
+    /// others can execute straight away.
+    pub fn queue_proposal(ctx: Context<QueueProposal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(proposal.status == ProposalStatus::Voting, ErrorCode::ProposalNotActive);
+        require!(now >= proposal.voting_ends_at, ErrorCode::VotingStillOpen);
+        
+        if !proposal.passed() {
+            proposal.status = ProposalStatus::Defeated;
+            emit!(ProposalDefeated {
+                proposal_id: proposal.id,
+                yes_votes: proposal.yes_votes,
+                no_votes: proposal.no_votes,
+                timestamp: now,
+            });
+            return Ok(());
+        }
+        
+        proposal.status = ProposalStatus::Queued;
+        proposal.eta = if proposal.action.is_sensitive() {
+            now + ctx.accounts.proposal_config.timelock_delay_secs
+        } else {
+            now
+        };
+        
+        emit!(ProposalQueued {
+            proposal_id: proposal.id,
+            action: proposal.action.clone(),
+            eta: proposal.eta,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Cancel a queued proposal before its eta (university authority only)
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(proposal.is_pending(now), ErrorCode::ProposalNotCancellable);
+        proposal.status = ProposalStatus::Cancelled;
+        
+        emit!(ProposalCancelled {
+            proposal_id: proposal.id,
+            cancelled_by: ctx.accounts.university_authority.key(),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Vote to cancel a queued proposal before its eta (active REC validators, once each).
+    /// The proposal is cancelled once a majority of active validators agree.
+    pub fn vote_cancel_proposal(ctx: Context<VoteCancelProposal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(proposal.is_pending(now), ErrorCode::ProposalNotCancellable);
+        
+        proposal.cancel_votes += 1;
+        
+        let vote_record = &mut ctx.accounts.cancel_vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.approve = true;
+        vote_record.voted_at = now;
+        
+        emit!(CancelVoteCast {
+            proposal_id: proposal.id,
+            voter: vote_record.voter,
+            cancel_votes: proposal.cancel_votes,
+            timestamp: now,
+        });
+        
+        if proposal.cancel_votes as u32 * 2 > ctx.accounts.poa_config.active_rec_validator_count {
+            proposal.status = ProposalStatus::Cancelled;
+            emit!(ProposalCancelled {
+                proposal_id: proposal.id,
+                cancelled_by: ctx.accounts.voter.key(),
+                timestamp: now,
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Carry out a queued proposal once its eta has passed (permissionless)
+    ///
+    /// Actions on other programs are CPIs signed by the governance authority PDA, which
+    /// must hold the target's authority. Remaining accounts are the target program
+    /// followed by its state account (the trading market or oracle data). Validator
+    /// additions and removals instead take the validator's PDA, whose rent the executor
+    /// pays or is refunded. Program upgrades take the program record, program, program
+    /// data, buffer, rent and clock sysvars and the upgradeable loader; the buffer's rent
+    /// goes to the executor.
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(proposal.status == ProposalStatus::Queued, ErrorCode::ProposalNotQueued);
+        require!(now >= proposal.eta, ErrorCode::TimelockNotExpired);
+        require!(!ctx.accounts.pause_config.is_paused(PauseScope::Global), ErrorCode::GovernancePaused);
+        
+        apply_action(
+            proposal.action.clone(),
+            &mut ctx.accounts.poa_config,
+            &mut ctx.accounts.protocol_params,
+            &ctx.accounts.governance_authority,
+            ctx.bumps.governance_authority,
+            &ctx.accounts.executor,
+            &ctx.accounts.system_program,
+            ctx.remaining_accounts,
+        )?;
+        
+        proposal.status = ProposalStatus::Executed;
+        proposal.executed_at = now;
+        
+        emit!(ProposalExecuted {
+            proposal_id: proposal.id,
+            executor: ctx.accounts.executor.key(),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Start tracking a program whose upgrade authority has been handed to the governance
+    /// authority PDA (university authority only)
+    ///
+    /// `program_hash` is the sha256 of the deployed program binary, recorded as the first
+    /// entry of its deployment history. From then on the program can only be upgraded by
+    /// an `UpgradeProgram` proposal.
+    pub fn register_program(ctx: Context<RegisterProgram>, program_hash: [u8; 32]) -> Result<()> {
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let program_record = &mut ctx.accounts.program_record;
+        program_record.program = ctx.accounts.program.key();
+        program_record.upgrade_count = 0;
+        program_record.deployments = Vec::new();
+        program_record.record_deployment(program_hash, now);
+        
+        emit!(ProgramRegistered {
+            program: program_record.program,
+            program_hash,
             authority: ctx.accounts.university_authority.key(),
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: now,
         });
         
         Ok(())
     }
     
-    /// Reactivate a REC validator
-    pub fn reactivate_rec_validator(
-        ctx: Context<ReactivateRecValidator>,
-        validator_pubkey: Pubkey,
+    /// Set the bond, voting window and bond treasury for disputes (university authority only)
+    pub fn initialize_disputes(
+        ctx: Context<InitializeDisputes>,
+        bond_lamports: u64,
+        voting_period_secs: i64,
+        treasury: Pubkey,
     ) -> Result<()> {
-        let poa_config = &mut ctx.accounts.poa_config;
+        require!(
+            (MIN_VOTING_PERIOD_SECS..=MAX_VOTING_PERIOD_SECS).contains(&voting_period_secs),
+            ErrorCode::InvalidVotingPeriod
+        );
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        
+        let dispute_config = &mut ctx.accounts.dispute_config;
+        dispute_config.dispute_count = 0;
+        dispute_config.bond_lamports = bond_lamports;
+        dispute_config.voting_period_secs = voting_period_secs;
+        dispute_config.treasury = treasury;
         
+        Ok(())
+    }
+    
+    /// Dispute a trade or meter reading, posting the configured bond
+    ///
+    /// The subject names the remedy applied if validators uphold the dispute. The bond is
+    /// returned then, and forfeited to the governance authority if they reject it.
+    pub fn open_dispute(
+        ctx: Context<OpenDispute>,
+        subject: DisputeSubject,
+        evidence_uris: Vec<String>,
+    ) -> Result<()> {
         require!(
-            ctx.accounts.university_authority.key() == poa_config.university_authority,
-            ErrorCode::UnauthorizedAuthority
+            !evidence_uris.is_empty()
+                && evidence_uris.len() <= MAX_EVIDENCE_URIS
+                && evidence_uris.iter().all(|uri| !uri.is_empty() && uri.len() <= MAX_EVIDENCE_URI_LEN),
+            ErrorCode::InvalidEvidence
         );
+        require!(subject.has_remedy(), ErrorCode::InvalidDisputeRemedy);
         
-        let validator = poa_config.authorized_rec_validators.iter_mut()
-            .find(|v| v.pubkey == validator_pubkey)
-            .ok_or(ErrorCode::ValidatorNotFound)?;
+        let bond = ctx.accounts.dispute_config.bond_lamports;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.claimant.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
         
-        require!(!validator.active, ErrorCode::ValidatorAlreadyActive);
+        let now = Clock::get()?.unix_timestamp;
+        let dispute_config = &mut ctx.accounts.dispute_config;
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.id = dispute_config.dispute_count;
+        dispute.claimant = ctx.accounts.claimant.key();
+        dispute.subject = subject;
+        dispute.evidence_uris = evidence_uris;
+        dispute.bond = bond;
+        dispute.status = DisputeStatus::Voting;
+        dispute.uphold_votes = 0;
+        dispute.reject_votes = 0;
+        dispute.quorum = ctx.accounts.poa_config.majority_quorum();
+        dispute.created_at = now;
+        dispute.voting_ends_at = now + dispute_config.voting_period_secs;
+        dispute.resolved_at = 0;
         
-        validator.active = true;
+        dispute_config.dispute_count += 1;
         
-        emit!(RecValidatorReactivated {
-            validator: validator_pubkey,
-            authority: ctx.accounts.university_authority.key(),
-            timestamp: Clock::get()?.unix_timestamp,
+        emit!(DisputeOpened {
+            dispute_id: dispute.id,
+            claimant: dispute.claimant,
+            subject: dispute.subject.clone(),
+            bond,
+            voting_ends_at: dispute.voting_ends_at,
+            timestamp: now,
         });
         
         Ok(())
     }
     
-    /// Get validator information
-    pub fn get_validator_info(ctx: Context<GetValidatorInfo>) -> Result<Vec<RecValidatorInfo>> {
-        let poa_config = &ctx.accounts.poa_config;
-        Ok(poa_config.authorized_rec_validators.clone())
+    /// Vote to uphold or reject a dispute within its voting window (active REC validators, once each)
+    pub fn vote_dispute(ctx: Context<VoteDispute>, uphold: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let dispute = &mut ctx.accounts.dispute;
+        
+        require!(dispute.status == DisputeStatus::Voting, ErrorCode::DisputeNotActive);
+        require!(now < dispute.voting_ends_at, ErrorCode::VotingClosed);
+        
+        if uphold {
+            dispute.uphold_votes += 1;
+        } else {
+            dispute.reject_votes += 1;
+        }
+        
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = dispute.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.approve = uphold;
+        vote_record.voted_at = now;
+        
+        emit!(DisputeVoteCast {
+            dispute_id: dispute.id,
+            voter: vote_record.voter,
+            uphold,
+            timestamp: now,
+        });
+        
+        Ok(())
     }
     
-    /// Verify if a validator is authorized and active
-    pub fn is_authorized_rec_validator(
-        ctx: Context<IsAuthorizedRecValidator>,
-        validator_pubkey: Pubkey,
-    ) -> Result<bool> {
-        let poa_config = &ctx.accounts.poa_config;
+    /// Close voting on a dispute and apply its outcome (permissionless)
+    ///
+    /// An upheld trade dispute refunds the buyer through trading::refund_trade, paid from
+    /// the governance authority PDA, and an upheld reading dispute corrects the meter
+    /// through oracle::correct_meter_reading and counts an overturned attestation against
+    /// the validator who activated the meter. Remaining accounts are the target program,
+    /// its state account and the disputed trade record or meter, followed by the buyer
+    /// and system program for a refund or the correction's registry accounts. Rejected
+    /// disputes forfeit their bond to the dispute treasury.
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let dispute = &mut ctx.accounts.dispute;
+        
+        require!(dispute.status == DisputeStatus::Voting, ErrorCode::DisputeNotActive);
+        require!(now >= dispute.voting_ends_at, ErrorCode::VotingStillOpen);
+        require!(!ctx.accounts.pause_config.is_paused(PauseScope::Global), ErrorCode::GovernancePaused);
+        
+        let authority = ctx.accounts.governance_authority.to_account_info();
+        let upheld = dispute.upheld();
+        if upheld {
+            require!(
+                ctx.remaining_accounts.get(2).map(|target| target.key()) == Some(dispute.subject.target()),
+                ErrorCode::InvalidDisputeTarget
+            );
+            let (program_id, state_seed, data) = match dispute.subject {
+                DisputeSubject::Trade { refund_amount, .. } => (
+                    TRADING_PROGRAM_ID,
+                    b"market".as_ref(),
+                    instruction_data("refund_trade", &refund_amount)?,
+                ),
+                DisputeSubject::Reading { reading_timestamp, generation_delta, consumption_delta, .. } => (
+                    ORACLE_PROGRAM_ID,
+                    b"oracle_data".as_ref(),
+                    instruction_data(
+                        "correct_meter_reading",
+                        &(reading_timestamp, generation_delta, consumption_delta),
+                    )?,
+                ),
+            };
+            invoke_governed(
+                program_id,
+                state_seed,
+                data,
+                ctx.remaining_accounts,
+                &authority,
+                ctx.bumps.governance_authority,
+            )?;
+            
+            if let DisputeSubject::Reading { .. } = dispute.subject {
+                overturn_meter_attestation(
+                    &ctx.remaining_accounts[2],
+                    &mut ctx.accounts.poa_config,
+                    ctx.accounts.rec_validator.as_ref(),
+                )?;
+            }
+        }
+        
+        let bond_to = if upheld {
+            ctx.accounts.claimant.to_account_info()
+        } else {
+            ctx.accounts.treasury.to_account_info()
+        };
+        pay_out_lamports(&dispute.to_account_info(), &bond_to, dispute.bond)?;
+        dispute.status = if upheld { DisputeStatus::Upheld } else { DisputeStatus::Rejected };
+        dispute.resolved_at = now;
         
-        let is_authorized = poa_config.authorized_rec_validators.iter().any(|v| 
-            v.pubkey == validator_pubkey && 
-            v.active && 
-            v.certification_authority
+        emit!(DisputeResolved {
+            dispute_id: dispute.id,
+            upheld,
+            uphold_votes: dispute.uphold_votes,
+            reject_votes: dispute.reject_votes,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Set up token-weighted referenda on `token_mint`, the energy token (university authority only)
+    ///
+    /// Votes need `min_turnout` tokens in total, in base units, for a referendum to pass.
+    pub fn initialize_referenda(
+        ctx: Context<InitializeReferenda>,
+        voting_period_secs: i64,
+        min_turnout: u64,
+    ) -> Result<()> {
+        require!(
+            (MIN_VOTING_PERIOD_SECS..=MAX_VOTING_PERIOD_SECS).contains(&voting_period_secs),
+            ErrorCode::InvalidVotingPeriod
         );
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        
+        let referendum_config = &mut ctx.accounts.referendum_config;
+        referendum_config.token_mint = ctx.accounts.token_mint.key();
+        referendum_config.referendum_count = 0;
+        referendum_config.voting_period_secs = voting_period_secs;
+        referendum_config.min_turnout = min_turnout;
         
-        Ok(is_authorized)
+        Ok(())
     }
-}
-
-// Account structs
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + PoAConfig::LEN,
-        seeds = [b"poa_config"],
-        bump
-    )]
-    pub poa_config: Account<'info, PoAConfig>,
     
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// Open the caller's token lock, which holds the energy tokens they vote with
+    pub fn open_token_lock(ctx: Context<OpenTokenLock>) -> Result<()> {
+        let token_lock = &mut ctx.accounts.token_lock;
+        token_lock.owner = ctx.accounts.owner.key();
+        token_lock.amount = 0;
+        token_lock.locked_at = 0;
+        token_lock.prior_amount = 0;
+        token_lock.prior_locked_at = 0;
+        token_lock.unlock_at = 0;
+        
+        Ok(())
+    }
     
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct EmergencyPause<'info> {
-    #[account(mut)]
-    pub poa_config: Account<'info, PoAConfig>,
+    /// Lock energy tokens for voting. They count toward referenda opened after this.
+    pub fn lock_tokens(ctx: Context<LockTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidLockAmount);
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    to: ctx.accounts.referendum_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let token_lock = &mut ctx.accounts.token_lock;
+        token_lock.deposit(amount, now)?;
+        
+        emit!(TokensLocked {
+            owner: token_lock.owner,
+            amount,
+            total_locked: token_lock.amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
     
-    #[account(constraint = authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct EmergencyUnpause<'info> {
-    #[account(mut)]
-    pub poa_config: Account<'info, PoAConfig>,
+    /// Withdraw locked tokens once every referendum the lock voted in has closed
+    pub fn unlock_tokens(ctx: Context<UnlockTokens>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let token_lock = &mut ctx.accounts.token_lock;
+        require!(now >= token_lock.unlock_at, ErrorCode::TokensStillLocked);
+        require!(amount > 0, ErrorCode::InvalidLockAmount);
+        token_lock.withdraw(amount)?;
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.referendum_vault.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.referendum_vault.to_account_info(),
+                },
+                &[&[b"referendum_vault", &[ctx.bumps.referendum_vault]]],
+            ),
+            amount,
+        )?;
+        
+        emit!(TokensUnlocked {
+            owner: token_lock.owner,
+            amount,
+            total_locked: token_lock.amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
     
-    #[account(constraint = authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
-    pub authority: Signer<'info>,
-}
-#[derive(Accounts)]
-pub struct InitializePoAWithRec<'info> {
-    #[account(
+    /// Put a question to energy token holders (active REC validators only)
+    ///
+    /// Advisory referenda only record the community's view; binding ones carry a market
+    /// action that executes if they pass, after the same timelock as a validator proposal.
+    pub fn create_referendum(
+        ctx: Context<CreateReferendum>,
+        kind: ReferendumKind,
+        question: String,
+    ) -> Result<()> {
+        let valid_kind = match &kind {
+            ReferendumKind::Advisory => true,
+            ReferendumKind::Binding { action } => action.is_valid() && action.is_community_action(),
+        };
+        require!(
+            !question.is_empty() && question.len() <= MAX_DESCRIPTION_LEN && valid_kind,
+            ErrorCode::InvalidProposal
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let referendum_config = &mut ctx.accounts.referendum_config;
+        let referendum = &mut ctx.accounts.referendum;
+        referendum.id = referendum_config.referendum_count;
+        referendum.proposer = ctx.accounts.proposer.key();
+        referendum.kind = kind;
+        referendum.question = question;
+        referendum.status = ReferendumStatus::Voting;
+        referendum.yes_weight = 0;
+        referendum.no_weight = 0;
+        referendum.voter_count = 0;
+        referendum.min_turnout = referendum_config.min_turnout;
+        referendum.snapshot_at = now;
+        referendum.voting_ends_at = now + referendum_config.voting_period_secs;
+        referendum.eta = 0;
+        referendum.executed_at = 0;
+        
+        referendum_config.referendum_count += 1;
+        
+        emit!(ReferendumCreated {
+            referendum_id: referendum.id,
+            proposer: referendum.proposer,
+            binding: referendum.is_binding(),
+            voting_ends_at: referendum.voting_ends_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Vote with the tokens locked before the referendum opened (once per holder)
+    ///
+    /// The tokens stay locked until voting ends, so they cannot be moved to another
+    /// wallet and counted again.
+    pub fn cast_referendum_vote(ctx: Context<CastReferendumVote>, approve: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let referendum = &mut ctx.accounts.referendum;
+        
+        require!(referendum.status == ReferendumStatus::Voting, ErrorCode::ReferendumNotActive);
+        require!(now < referendum.voting_ends_at, ErrorCode::VotingClosed);
+        
+        let token_lock = &mut ctx.accounts.token_lock;
+        let weight = token_lock.weight_at(referendum.snapshot_at);
+        require!(weight > 0, ErrorCode::NoVotingWeight);
+        token_lock.unlock_at = token_lock.unlock_at.max(referendum.voting_ends_at);
+        
+        if approve {
+            referendum.yes_weight = referendum.yes_weight.checked_add(weight).ok_or(ErrorCode::InvalidLockAmount)?;
+        } else {
+            referendum.no_weight = referendum.no_weight.checked_add(weight).ok_or(ErrorCode::InvalidLockAmount)?;
+        }
+        referendum.voter_count += 1;
+        
+        let ballot = &mut ctx.accounts.ballot;
+        ballot.referendum = referendum.key();
+        ballot.voter = ctx.accounts.voter.key();
+        ballot.approve = approve;
+        ballot.weight = weight;
+        ballot.voted_at = now;
+        
+        emit!(ReferendumVoteCast {
+            referendum_id: referendum.id,
+            voter: ballot.voter,
+            approve,
+            weight,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Close voting and record the result (permissionless)
+    ///
+    /// A passed binding referendum is scheduled like a validator proposal, waiting out the
+    /// timelock if its action is sensitive.
+    pub fn finalize_referendum(ctx: Context<FinalizeReferendum>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let referendum = &mut ctx.accounts.referendum;
+        
+        require!(referendum.status == ReferendumStatus::Voting, ErrorCode::ReferendumNotActive);
+        require!(now >= referendum.voting_ends_at, ErrorCode::VotingStillOpen);
+        
+        let passed = referendum.passed();
+        referendum.status = if passed { ReferendumStatus::Passed } else { ReferendumStatus::Rejected };
+        if let ReferendumKind::Binding { action } = &referendum.kind {
+            if passed {
+                referendum.eta = if action.is_sensitive() {
+                    now + ctx.accounts.proposal_config.timelock_delay_secs
+                } else {
+                    now
+                };
+            }
+        }
+        
+        emit!(ReferendumFinalized {
+            referendum_id: referendum.id,
+            passed,
+            yes_weight: referendum.yes_weight,
+            no_weight: referendum.no_weight,
+            voter_count: referendum.voter_count,
+            eta: referendum.eta,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Carry out a passed binding referendum once its eta has passed (permissionless).
+    /// Remaining accounts are as for `execute_proposal`.
+    pub fn execute_referendum<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteReferendum<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let referendum = &mut ctx.accounts.referendum;
+        
+        require!(referendum.status == ReferendumStatus::Passed, ErrorCode::ReferendumNotExecutable);
+        let ReferendumKind::Binding { action } = referendum.kind.clone() else {
+            return err!(ErrorCode::ReferendumNotExecutable);
+        };
+        require!(now >= referendum.eta, ErrorCode::TimelockNotExpired);
+        require!(!ctx.accounts.pause_config.is_paused(PauseScope::Global), ErrorCode::GovernancePaused);
+        
+        apply_action(
+            action,
+            &mut ctx.accounts.poa_config,
+            &mut ctx.accounts.protocol_params,
+            &ctx.accounts.governance_authority,
+            ctx.bumps.governance_authority,
+            &ctx.accounts.executor,
+            &ctx.accounts.system_program,
+            ctx.remaining_accounts,
+        )?;
+        
+        referendum.status = ReferendumStatus::Executed;
+        referendum.executed_at = now;
+        
+        emit!(ReferendumExecuted {
+            referendum_id: referendum.id,
+            executor: ctx.accounts.executor.key(),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Cancel a passed binding referendum before its eta (university authority only)
+    pub fn cancel_referendum(ctx: Context<CancelReferendum>) -> Result<()> {
+        ctx.accounts.poa_config.require_authority(&ctx.accounts.university_authority, ctx.remaining_accounts)?;
+        let now = Clock::get()?.unix_timestamp;
+        let referendum = &mut ctx.accounts.referendum;
+        
+        require!(
+            referendum.status == ReferendumStatus::Passed && referendum.is_binding() && now < referendum.eta,
+            ErrorCode::ReferendumNotExecutable
+        );
+        referendum.status = ReferendumStatus::Cancelled;
+        
+        emit!(ReferendumCancelled {
+            referendum_id: referendum.id,
+            cancelled_by: ctx.accounts.university_authority.key(),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Get validator information
+    pub fn get_validator_info(ctx: Context<GetValidatorInfo>, _validator_pubkey: Pubkey) -> Result<RecValidator> {
+        Ok((*ctx.accounts.rec_validator).clone())
+    }
+    
+    /// Verify if a validator is authorized and active, from its PDA alone
+    pub fn is_authorized_rec_validator(
+        ctx: Context<IsAuthorizedRecValidator>,
+        _validator_pubkey: Pubkey,
+    ) -> Result<bool> {
+        is_active_rec_validator(&ctx.accounts.rec_validator)
+    }
+}
+
+/// Carry out a passed proposal's or binding referendum's action. Actions on other programs
+/// are CPIs signed by the governance authority PDA; validator additions, removals and
+/// deactivations take the validator's PDA as the first remaining account.
+#[allow(clippy::too_many_arguments)]
+fn apply_action<'info>(
+    action: ProposalAction,
+    poa_config: &mut Account<'info, PoAConfig>,
+    protocol_params: &mut Account<'info, ProtocolParams>,
+    governance_authority: &UncheckedAccount<'info>,
+    bump: u8,
+    executor: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let authority = governance_authority.to_account_info();
+    match action {
+        ProposalAction::UpdateMarketParams { market_fee_bps, clearing_enabled } => {
+            invoke_governed(
+                TRADING_PROGRAM_ID,
+                b"market",
+                instruction_data("set_clearing_enabled", &clearing_enabled)?,
+                remaining_accounts,
+                &authority,
+                bump,
+            )?;
+            protocol_params.apply(|params| params.market_fee_bps = market_fee_bps)?;
+        }
+        ProposalAction::AddRecValidator { validator, department } => {
+            poa_config.register_validator()?;
+            create_rec_validator(
+                rec_validator_target(remaining_accounts, &validator)?,
+                executor,
+                system_program,
+                RecValidator::new(validator, department, Clock::get()?.unix_timestamp),
+            )?;
+        }
+        ProposalAction::RemoveRecValidator { validator } => {
+            let mut rec_validator = Account::<RecValidator>::try_from(
+                rec_validator_target(remaining_accounts, &validator)?,
+            )?;
+            poa_config.unregister_validator(&mut rec_validator)?;
+            rec_validator.exit(&crate::ID)?;
+            
+            emit!(RecValidatorRemoved {
+                validator,
+                authority: authority.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        ProposalAction::SetMinRecValidators { min_validators } => {
+            let old_min = poa_config.set_min_validators(min_validators)?;
+            protocol_params.apply(|params| params.min_rec_validators = min_validators)?;
+            
+            emit!(MinValidatorsUpdated {
+                old_min,
+                new_min: min_validators,
+                authority: authority.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        ProposalAction::DeactivateRecValidator { validator } => {
+            let mut rec_validator = Account::<RecValidator>::try_from(
+                rec_validator_target(remaining_accounts, &validator)?,
+            )?;
+            poa_config.deactivate_validator(&mut rec_validator)?;
+            rec_validator.exit(&crate::ID)?;
+            
+            emit!(RecValidatorDeactivated {
+                validator,
+                authority: authority.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        ProposalAction::ReactivateRecValidator { validator } => {
+            let mut rec_validator = Account::<RecValidator>::try_from(
+                rec_validator_target(remaining_accounts, &validator)?,
+            )?;
+            poa_config.reactivate_validator(&mut rec_validator)?;
+            rec_validator.exit(&crate::ID)?;
+            
+            emit!(RecValidatorReactivated {
+                validator,
+                authority: authority.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        ProposalAction::ReportMissedAttestation { validator } => {
+            let mut rec_validator = Account::<RecValidator>::try_from(
+                rec_validator_target(remaining_accounts, &validator)?,
+            )?;
+            apply_attestation(poa_config, &mut rec_validator, AttestationOutcome::Missed)?;
+            rec_validator.exit(&crate::ID)?;
+        }
+        ProposalAction::SetMinReliability { min_reliability_bps } => {
+            let old_min_bps = poa_config.min_reliability_bps;
+            poa_config.min_reliability_bps = min_reliability_bps;
+            
+            emit!(MinReliabilityUpdated {
+                old_min_bps,
+                new_min_bps: min_reliability_bps,
+                authority: authority.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        ProposalAction::UpdateOracleGateway { api_gateway } => {
+            protocol_params.apply(|params| params.oracle_reporter = api_gateway)?;
+        }
+        ProposalAction::UpdateProtocolParams { params } => {
+            poa_config.set_min_validators(params.min_rec_validators)?;
+            protocol_params.apply(|current| *current = params)?;
+        }
+        ProposalAction::UpgradeProgram { program, buffer, buffer_hash } => {
+            upgrade_program(program, buffer, buffer_hash, &authority, bump, executor, remaining_accounts)?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace a registered program's code with a buffer whose contents hash to
+/// `buffer_hash`, as its upgrade authority, and record the new hash. Hashing a whole
+/// binary needs the executor to request a raised compute budget.
+fn upgrade_program<'info>(
+    program: Pubkey,
+    buffer: Pubkey,
+    buffer_hash: [u8; 32],
+    authority: &AccountInfo<'info>,
+    bump: u8,
+    executor: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let [program_record_info, program_info, program_data_info, buffer_info, rent, clock, loader, ..] = remaining_accounts else {
+        return err!(ErrorCode::InvalidProposalTarget);
+    };
+    let mut program_record = Account::<ProgramRecord>::try_from(program_record_info)?;
+    require_keys_eq!(program_record.program, program, ErrorCode::InvalidProposalTarget);
+    require_keys_eq!(program_info.key(), program, ErrorCode::InvalidProposalTarget);
+    require_keys_eq!(buffer_info.key(), buffer, ErrorCode::InvalidProposalTarget);
+    require_keys_eq!(loader.key(), bpf_loader_upgradeable::ID, ErrorCode::InvalidProposalTarget);
+    
+    // The buffer is closed by the upgrade, so check what it holds first
+    {
+        let buffer_state = Account::<UpgradeableLoaderState>::try_from(buffer_info)?;
+        require!(
+            matches!(*buffer_state, UpgradeableLoaderState::Buffer { authority_address: Some(key) } if key == authority.key()),
+            ErrorCode::InvalidUpgradeBuffer
+        );
+        let data = buffer_info.try_borrow_data()?;
+        let code = data
+            .get(UpgradeableLoaderState::size_of_buffer_metadata()..)
+            .ok_or(ErrorCode::InvalidUpgradeBuffer)?;
+        require!(hash(code).to_bytes() == buffer_hash, ErrorCode::UpgradeHashMismatch);
+    }
+    
+    invoke_signed(
+        &bpf_loader_upgradeable::upgrade(&program, &buffer, &authority.key(), &executor.key()),
+        &[
+            program_data_info.clone(),
+            program_info.clone(),
+            buffer_info.clone(),
+            executor.to_account_info(),
+            rent.clone(),
+            clock.clone(),
+            authority.clone(),
+            loader.clone(),
+        ],
+        &[&[b"governance_authority", &[bump]]],
+    )?;
+    
+    let now = Clock::get()?.unix_timestamp;
+    program_record.upgrade_count += 1;
+    program_record.record_deployment(buffer_hash, now);
+    program_record.exit(&crate::ID)?;
+    
+    emit!(ProgramUpgraded {
+        program,
+        buffer,
+        program_hash: buffer_hash,
+        upgrade_count: program_record.upgrade_count,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Anchor instruction data: the method's sighash followed by its Borsh arguments
+fn instruction_data<T: AnchorSerialize>(method: &str, args: &T) -> Result<Vec<u8>> {
+    let mut data = hash(format!("global:{}", method).as_bytes()).to_bytes()[..8].to_vec();
+    args.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Call a `(state, authority, ..)` instruction on a governed program as the governance
+/// authority, after checking the program and its singleton state PDA. Remaining accounts
+/// after the state are passed on as the instruction's further accounts.
+fn invoke_governed<'info>(
+    program_id: Pubkey,
+    state_seed: &[u8],
+    data: Vec<u8>,
+    remaining_accounts: &[AccountInfo<'info>],
+    authority: &AccountInfo<'info>,
+    bump: u8,
+) -> Result<()> {
+    let [program, state, rest @ ..] = remaining_accounts else {
+        return err!(ErrorCode::InvalidProposalTarget);
+    };
+    require_keys_eq!(program.key(), program_id, ErrorCode::InvalidProposalTarget);
+    let (expected_state, _) = Pubkey::find_program_address(&[state_seed], &program_id);
+    require_keys_eq!(state.key(), expected_state, ErrorCode::InvalidProposalTarget);
+    
+    let mut accounts = vec![
+        AccountMeta::new(state.key(), false),
+        AccountMeta { pubkey: authority.key(), is_signer: true, is_writable: authority.is_writable },
+    ];
+    accounts.extend(rest.iter().map(|info| AccountMeta {
+        pubkey: info.key(),
+        is_signer: false,
+        is_writable: info.is_writable,
+    }));
+    let mut account_infos = vec![state.clone(), authority.clone()];
+    account_infos.extend(rest.iter().cloned());
+    account_infos.push(program.clone());
+    
+    invoke_signed(
+        &Instruction { program_id, accounts, data },
+        &account_infos,
+        &[&[b"governance_authority", &[bump]]],
+    )?;
+    Ok(())
+}
+
+// Account structs
+#[derive(Accounts)]
+pub struct InitializePauseConfig<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + PauseConfig::INIT_SPACE,
+        seeds = [b"pause_config"],
+        bump
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(mut, constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauseGuardian<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"pause_config"], bump)]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyPause<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"pause_config"], bump)]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(
+        constraint = authority.key() == pause_config.guardian
+            || poa_config.is_authority_signer(&authority.key()) @ ErrorCode::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyUnpause<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"pause_config"], bump)]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(constraint = poa_config.is_authority_signer(&authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub authority: Signer<'info>,
+}
+#[derive(Accounts)]
+pub struct InitializePoAWithRec<'info> {
+    #[account(
         init,
         payer = university_authority,
-        space = 8 + PoAConfig::LEN,
+        space = 8 + PoAConfig::INIT_SPACE,
         seeds = [b"poa_config"],
         bump
     )]
     pub poa_config: Account<'info, PoAConfig>,
     
-    #[account(mut, constraint = university_authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + ProtocolParams::INIT_SPACE,
+        seeds = [b"protocol_params"],
+        bump
+    )]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(mut)]
     pub university_authority: Signer<'info>,
     
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub program: Program<'info, crate::program::Governance>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    
     /// CHECK: University Sustainability Office validator
     pub sustainability_validator: AccountInfo<'info>,
     /// CHECK: University Engineering Department validator
@@ -357,111 +1473,1531 @@ pub struct InitializePoAWithRec<'info> {
     /// CHECK: University Facilities Management validator
     pub facilities_validator: AccountInfo<'info>,
     
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + RecValidator::INIT_SPACE,
+        seeds = [b"rec_validator", sustainability_validator.key().as_ref()],
+        bump
+    )]
+    pub sustainability_rec_validator: Account<'info, RecValidator>,
+    
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + RecValidator::INIT_SPACE,
+        seeds = [b"rec_validator", engineering_validator.key().as_ref()],
+        bump
+    )]
+    pub engineering_rec_validator: Account<'info, RecValidator>,
+    
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + RecValidator::INIT_SPACE,
+        seeds = [b"rec_validator", facilities_validator.key().as_ref()],
+        bump
+    )]
+    pub facilities_rec_validator: Account<'info, RecValidator>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(validator_pubkey: Pubkey)]
+pub struct AddRecValidator<'info> {
+    #[account(mut, seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    /// Fails to initialize if the validator is already authorized
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + RecValidator::INIT_SPACE,
+        seeds = [b"rec_validator", validator_pubkey.as_ref()],
+        bump
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    /// CHECK: Only read for existence; bootstrapping ends once proposals are set up
+    #[account(seeds = [b"proposal_config"], bump)]
+    pub proposal_config: UncheckedAccount<'info>,
+    
+    #[account(mut, constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(validator: Pubkey)]
+pub struct RecordAttestation<'info> {
+    #[account(mut, seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"rec_validator", validator.as_ref()], bump)]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    /// Oracle or registry program PDA; only those programs can produce this signature via CPI
+    #[account(constraint = is_attestation_reporter(&reporter.key()) @ ErrorCode::UnauthorizedAttestationReporter)]
+    pub reporter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenValidatorStake<'info> {
+    /// Only exists for authorized validators
+    #[account(seeds = [b"rec_validator", validator.key().as_ref()], bump)]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(
+        init,
+        payer = validator,
+        space = 8 + ValidatorStake::INIT_SPACE,
+        seeds = [b"validator_stake", validator.key().as_ref()],
+        bump
+    )]
+    pub validator_stake: Account<'info, ValidatorStake>,
+    
+    #[account(mut)]
+    pub validator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositStake<'info> {
+    #[account(mut, seeds = [b"validator_stake", validator.key().as_ref()], bump)]
+    pub validator_stake: Account<'info, ValidatorStake>,
+    
+    #[account(mut)]
+    pub validator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    /// Kept as a tombstone after removal until the stake is settled
+    #[account(seeds = [b"rec_validator", validator.key().as_ref()], bump)]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(mut, seeds = [b"validator_stake", validator.key().as_ref()], bump)]
+    pub validator_stake: Account<'info, ValidatorStake>,
+    
+    #[account(mut)]
+    pub validator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashValidatorStake<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"validator_stake", validator_stake.validator.as_ref()], bump)]
+    pub validator_stake: Account<'info, ValidatorStake>,
+    
+    #[account(seeds = [b"rec_validator", validator_stake.validator.as_ref()], bump)]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(seeds = [b"dispute_config"], bump)]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    
+    /// CHECK: Receives the slashed lamports
+    #[account(mut, address = dispute_config.treasury @ ErrorCode::InvalidDisputeTarget)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRecValidator<'info> {
+    #[account(
+        mut,
+        seeds = [b"rec_validator", validator.key().as_ref()],
+        bump,
+        close = validator,
+        constraint = rec_validator.removed @ ErrorCode::ValidatorNotRemoved
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    /// CHECK: The validator's stake PDA, settled when it was never opened or is empty
+    #[account(seeds = [b"validator_stake", validator.key().as_ref()], bump)]
+    pub validator_stake: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub validator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut, seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut, seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(constraint = poa_config.pending_authority_signers.contains(&new_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProposals<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + ProposalConfig::INIT_SPACE,
+        seeds = [b"proposal_config"],
+        bump
+    )]
+    pub proposal_config: Account<'info, ProposalConfig>,
+    
+    #[account(mut, constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        seeds = [b"rec_validator", proposer.key().as_ref()],
+        bump,
+        constraint = rec_validator.is_active() @ ErrorCode::UnauthorizedValidator
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(mut, seeds = [b"proposal_config"], bump)]
+    pub proposal_config: Account<'info, ProposalConfig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", proposal_config.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-pub struct AddRecValidator<'info> {
-    #[account(mut, has_one = university_authority @ ErrorCode::UnauthorizedAuthority)]
-    pub poa_config: Account<'info, PoAConfig>,
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        seeds = [b"rec_validator", voter.key().as_ref()],
+        bump,
+        constraint = rec_validator.is_active() @ ErrorCode::UnauthorizedValidator
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump)]
+    pub proposal: Account<'info, Proposal>,
+    
+    /// Fails to initialize on a second vote by the same validator
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTimelockDelay<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"proposal_config"], bump)]
+    pub proposal_config: Account<'info, ProposalConfig>,
+    
+    #[account(constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueProposal<'info> {
+    #[account(seeds = [b"proposal_config"], bump)]
+    pub proposal_config: Account<'info, ProposalConfig>,
+    
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump)]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VoteCancelProposal<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        seeds = [b"rec_validator", voter.key().as_ref()],
+        bump,
+        constraint = rec_validator.is_active() @ ErrorCode::UnauthorizedValidator
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump)]
+    pub proposal: Account<'info, Proposal>,
+    
+    /// Fails to initialize on a second cancel vote by the same validator
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [b"cancel_vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub cancel_vote_record: Account<'info, VoteRecord>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut, seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"protocol_params"], bump)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump)]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(seeds = [b"pause_config"], bump)]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// CHECK: PDA that holds authority over governed programs; signs their CPIs
+    #[account(seeds = [b"governance_authority"], bump)]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// The program data must already name the governance authority PDA as upgrade authority,
+/// e.g. after `solana program set-upgrade-authority`
+#[derive(Accounts)]
+pub struct RegisterProgram<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + ProgramRecord::INIT_SPACE,
+        seeds = [b"program_record", program.key().as_ref()],
+        bump
+    )]
+    pub program_record: Account<'info, ProgramRecord>,
+    
+    /// CHECK: An upgradeable program, identified through its program data account
+    #[account(executable)]
+    pub program: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [program.key().as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(governance_authority.key()) @ ErrorCode::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    /// CHECK: PDA that holds authority over governed programs
+    #[account(seeds = [b"governance_authority"], bump)]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    #[account(mut, constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeDisputes<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + DisputeConfig::INIT_SPACE,
+        seeds = [b"dispute_config"],
+        bump
+    )]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    
+    #[account(mut, constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"dispute_config"], bump)]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", dispute_config.dispute_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteDispute<'info> {
+    #[account(
+        seeds = [b"rec_validator", voter.key().as_ref()],
+        bump,
+        constraint = rec_validator.is_active() @ ErrorCode::UnauthorizedValidator
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(mut, seeds = [b"dispute", dispute.id.to_le_bytes().as_ref()], bump)]
+    pub dispute: Account<'info, Dispute>,
+    
+    /// Fails to initialize on a second vote by the same validator
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [b"dispute_vote", dispute.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.id.to_le_bytes().as_ref()], bump)]
+    pub dispute: Account<'info, Dispute>,
+    
+    #[account(seeds = [b"pause_config"], bump)]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(seeds = [b"dispute_config"], bump)]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    
+    #[account(mut, seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    /// CHECK: PDA that signs the remedy CPI and pays trade refunds
+    #[account(mut, seeds = [b"governance_authority"], bump)]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the bond back if the dispute is upheld
+    #[account(mut, constraint = claimant.key() == dispute.claimant @ ErrorCode::InvalidDisputeTarget)]
+    pub claimant: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the bond if the dispute is rejected
+    #[account(mut, address = dispute_config.treasury @ ErrorCode::InvalidDisputeTarget)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// CHECK: The disputed meter's activating validator, for upheld reading disputes;
+    /// checked against the meter in the handler
+    #[account(mut)]
+    pub rec_validator: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeReferenda<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + ReferendumConfig::INIT_SPACE,
+        seeds = [b"referendum_config"],
+        bump
+    )]
+    pub referendum_config: Account<'info, ReferendumConfig>,
+    
+    pub token_mint: Account<'info, Mint>,
+    
+    /// Holds all locked voting tokens; its own PDA is the owner
+    #[account(
+        init,
+        payer = university_authority,
+        token::mint = token_mint,
+        token::authority = referendum_vault,
+        seeds = [b"referendum_vault"],
+        bump
+    )]
+    pub referendum_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenTokenLock<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TokenLock::INIT_SPACE,
+        seeds = [b"token_lock", owner.key().as_ref()],
+        bump
+    )]
+    pub token_lock: Account<'info, TokenLock>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LockTokens<'info> {
+    #[account(mut, seeds = [b"token_lock", owner.key().as_ref()], bump)]
+    pub token_lock: Account<'info, TokenLock>,
+    
+    #[account(mut, seeds = [b"referendum_vault"], bump)]
+    pub referendum_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::authority = owner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockTokens<'info> {
+    #[account(mut, seeds = [b"token_lock", owner.key().as_ref()], bump)]
+    pub token_lock: Account<'info, TokenLock>,
+    
+    #[account(mut, seeds = [b"referendum_vault"], bump)]
+    pub referendum_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = referendum_vault.mint)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateReferendum<'info> {
+    #[account(
+        seeds = [b"rec_validator", proposer.key().as_ref()],
+        bump,
+        constraint = rec_validator.is_active() @ ErrorCode::UnauthorizedValidator
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(mut, seeds = [b"referendum_config"], bump)]
+    pub referendum_config: Account<'info, ReferendumConfig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Referendum::INIT_SPACE,
+        seeds = [b"referendum", referendum_config.referendum_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub referendum: Account<'info, Referendum>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastReferendumVote<'info> {
+    #[account(mut, seeds = [b"referendum", referendum.id.to_le_bytes().as_ref()], bump)]
+    pub referendum: Account<'info, Referendum>,
+    
+    #[account(mut, seeds = [b"token_lock", voter.key().as_ref()], bump)]
+    pub token_lock: Account<'info, TokenLock>,
+    
+    /// Fails to initialize on a second vote by the same holder
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + ReferendumBallot::INIT_SPACE,
+        seeds = [b"referendum_ballot", referendum.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub ballot: Account<'info, ReferendumBallot>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReferendum<'info> {
+    #[account(seeds = [b"proposal_config"], bump)]
+    pub proposal_config: Account<'info, ProposalConfig>,
+    
+    #[account(mut, seeds = [b"referendum", referendum.id.to_le_bytes().as_ref()], bump)]
+    pub referendum: Account<'info, Referendum>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteReferendum<'info> {
+    #[account(mut, seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"protocol_params"], bump)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(mut, seeds = [b"referendum", referendum.id.to_le_bytes().as_ref()], bump)]
+    pub referendum: Account<'info, Referendum>,
+    
+    #[account(seeds = [b"pause_config"], bump)]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// CHECK: PDA that holds authority over governed programs; signs their CPIs
+    #[account(seeds = [b"governance_authority"], bump)]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelReferendum<'info> {
+    #[account(seeds = [b"poa_config"], bump)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(mut, seeds = [b"referendum", referendum.id.to_le_bytes().as_ref()], bump)]
+    pub referendum: Account<'info, Referendum>,
+    
+    #[account(constraint = poa_config.is_authority_signer(&university_authority.key()) @ ErrorCode::UnauthorizedAuthority)]
+    pub university_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(validator_pubkey: Pubkey)]
+pub struct GetValidatorInfo<'info> {
+    #[account(seeds = [b"rec_validator", validator_pubkey.as_ref()], bump)]
+    pub rec_validator: Account<'info, RecValidator>,
+}
+
+#[derive(Accounts)]
+#[instruction(validator_pubkey: Pubkey)]
+pub struct IsAuthorizedRecValidator<'info> {
+    /// CHECK: The validator's PDA, read only if it exists
+    #[account(seeds = [b"rec_validator", validator_pubkey.as_ref()], bump)]
+    pub rec_validator: UncheckedAccount<'info>,
+}
+
+
+
+// Data structs
+#[account]
+#[derive(InitSpace)]
+pub struct PoAConfig {
+    #[max_len(MAX_AUTHORITY_SIGNERS)]
+    pub authority_signers: Vec<Pubkey>,          // University authority multisig members
+    pub authority_threshold: u8,                 // Member signatures required (M of N)
+    #[max_len(MAX_AUTHORITY_SIGNERS)]
+    pub pending_authority_signers: Vec<Pubkey>,  // Proposed rotation, empty when none
+    pub pending_authority_threshold: u8,
+    pub rec_validator_count: u32,                // `RecValidator` PDAs in existence
+    pub active_rec_validator_count: u32,
+    pub min_rec_validators: u8,
+    pub min_reliability_bps: u16,    // Auto-deactivation threshold, 0 disables it
+    pub created_at: i64,
+}
+
+impl PoAConfig {
+    pub fn set_sole_authority(&mut self, authority: Pubkey) {
+        self.authority_signers = vec![authority];
+        self.authority_threshold = 1;
+        self.pending_authority_signers = Vec::new();
+        self.pending_authority_threshold = 0;
+    }
+    
+    pub fn is_authority_signer(&self, key: &Pubkey) -> bool {
+        self.authority_signers.contains(key)
+    }
+    
+    /// Require `authority_threshold` distinct members to have signed, counting `authority`
+    /// and any co-signing members passed as remaining accounts
+    pub fn require_authority(&self, authority: &Signer, co_signers: &[AccountInfo]) -> Result<()> {
+        require!(
+            approvals(&self.authority_signers, authority, co_signers) >= self.authority_threshold as usize,
+            ErrorCode::InsufficientAuthoritySignatures
+        );
+        Ok(())
+    }
+    
+    pub fn propose_authority(&mut self, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        validate_multisig(&signers, threshold)?;
+        self.pending_authority_signers = signers;
+        self.pending_authority_threshold = threshold;
+        Ok(())
+    }
+    
+    /// Swap in the pending multisig once enough of its own members have signed
+    pub fn accept_authority(&mut self, authority: &Signer, co_signers: &[AccountInfo]) -> Result<()> {
+        require!(!self.pending_authority_signers.is_empty(), ErrorCode::NoPendingAuthority);
+        require!(
+            approvals(&self.pending_authority_signers, authority, co_signers)
+                >= self.pending_authority_threshold as usize,
+            ErrorCode::InsufficientAuthoritySignatures
+        );
+        
+        self.authority_signers = std::mem::take(&mut self.pending_authority_signers);
+        self.authority_threshold = self.pending_authority_threshold;
+        self.pending_authority_threshold = 0;
+        Ok(())
+    }
+    
+    /// Count a newly created validator PDA, which starts active
+    pub fn register_validator(&mut self) -> Result<()> {
+        self.rec_validator_count = self.rec_validator_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MaxValidatorsExceeded)?;
+        self.active_rec_validator_count += 1;
+        Ok(())
+    }
+    
+    /// Uncount a validator and leave its PDA as an inactive tombstone
+    pub fn unregister_validator(&mut self, validator: &mut RecValidator) -> Result<()> {
+        require!(!validator.removed, ErrorCode::ValidatorRemoved);
+        require!(
+            self.rec_validator_count > self.min_rec_validators as u32,
+            ErrorCode::InsufficientValidators
+        );
+        
+        self.rec_validator_count -= 1;
+        if validator.active {
+            self.active_rec_validator_count -= 1;
+            validator.active = false;
+            validator.deactivated_at = Clock::get()?.unix_timestamp;
+        }
+        validator.removed = true;
+        Ok(())
+    }
+    
+    /// Returns the previous minimum
+    pub fn set_min_validators(&mut self, new_min: u8) -> Result<u8> {
+        require!(
+            new_min > 0 && new_min as u32 <= self.rec_validator_count,
+            ErrorCode::InvalidMinValidators
+        );
+        
+        let old_min = self.min_rec_validators;
+        self.min_rec_validators = new_min;
+        Ok(old_min)
+    }
+    
+    /// Votes needed to decide: a majority of the active validators, never fewer than
+    /// `min_rec_validators`
+    pub fn majority_quorum(&self) -> u8 {
+        let majority = self.active_rec_validator_count / 2 + 1;
+        u8::try_from(majority).unwrap_or(u8::MAX).max(self.min_rec_validators)
+    }
+    
+    pub fn deactivate_validator(&mut self, validator: &mut RecValidator) -> Result<()> {
+        require!(validator.active, ErrorCode::ValidatorAlreadyInactive);
+        require!(
+            self.active_rec_validator_count > self.min_rec_validators as u32,
+            ErrorCode::InsufficientActiveValidators
+        );
+        
+        validator.active = false;
+        validator.deactivated_at = Clock::get()?.unix_timestamp;
+        self.active_rec_validator_count -= 1;
+        Ok(())
+    }
+    
+    pub fn reactivate_validator(&mut self, validator: &mut RecValidator) -> Result<()> {
+        require!(!validator.active, ErrorCode::ValidatorAlreadyActive);
+        require!(!validator.removed, ErrorCode::ValidatorRemoved);
+        
+        validator.active = true;
+        validator.deactivated_at = 0;
+        self.active_rec_validator_count += 1;
+        Ok(())
+    }
+    
+    /// Count an attestation outcome against `validator`, deactivating it if its reliability
+    /// has fallen below `min_reliability_bps`. Validators are never deactivated below
+    /// `min_rec_validators`, so governance keeps its quorum.
+    ///
+    /// Returns the validator's reliability and whether it was deactivated.
+    pub fn record_attestation(
+        &mut self,
+        validator: &mut RecValidator,
+        outcome: AttestationOutcome,
+    ) -> Result<(u16, bool)> {
+        match outcome {
+            AttestationOutcome::Certified => validator.attestations += 1,
+            AttestationOutcome::Missed => validator.missed_attestations += 1,
+            AttestationOutcome::Overturned => {
+                require!(
+                    validator.overturned_attestations < validator.attestations,
+                    ErrorCode::InvalidAttestationReport
+                );
+                validator.overturned_attestations += 1;
+            }
+        }
+        
+        let reliability_bps = validator.reliability_bps();
+        let deactivate = validator.active
+            && validator.expected_attestations() >= MIN_RELIABILITY_SAMPLE
+            && reliability_bps < self.min_reliability_bps
+            && self.active_rec_validator_count > self.min_rec_validators as u32;
+        if deactivate {
+            self.deactivate_validator(validator)?;
+        }
+        
+        Ok((reliability_bps, deactivate))
+    }
+}
+
+/// One REC certification authority, stored at `[b"rec_validator", pubkey]`
+#[account]
+#[derive(InitSpace)]
+pub struct RecValidator {
+    pub pubkey: Pubkey,
+    #[max_len(MAX_VALIDATOR_NAME_LEN)]
+    pub authority_name: String, // e.g., "University Sustainability Office"
+    pub certification_authority: bool,
+    pub active: bool,
+    pub added_at: i64,
+    pub attestations: u32,              // Certifications made
+    pub missed_attestations: u32,       // Certifications expected but not made
+    pub overturned_attestations: u32,   // Certifications later found false
+    pub deactivated_at: i64,            // Start of stake unbonding; 0 while active
+    pub removed: bool,                  // Tombstone kept until the stake is settled
+}
+
+impl RecValidator {
+    pub fn new(pubkey: Pubkey, authority_name: String, now: i64) -> Self {
+        Self {
+            pubkey,
+            authority_name,
+            certification_authority: true,
+            active: true,
+            added_at: now,
+            attestations: 0,
+            missed_attestations: 0,
+            overturned_attestations: 0,
+            deactivated_at: 0,
+            removed: false,
+        }
+    }
+    
+    /// Whether this validator is an active REC certification authority
+    pub fn is_active(&self) -> bool {
+        self.active && self.certification_authority
+    }
+    
+    fn expected_attestations(&self) -> u64 {
+        self.attestations as u64 + self.missed_attestations as u64
+    }
+    
+    /// Share of expected certifications that were made and have not been overturned
+    pub fn reliability_bps(&self) -> u16 {
+        let expected = self.expected_attestations();
+        if expected == 0 {
+            return 10_000;
+        }
+        let sound = self.attestations.saturating_sub(self.overturned_attestations) as u64;
+        (sound * 10_000 / expected) as u16
+    }
+}
+
+/// Whether the `RecValidator` PDA at `info` exists and is active; validators never
+/// added have no account
+fn is_active_rec_validator(info: &AccountInfo) -> Result<bool> {
+    if info.data_is_empty() {
+        return Ok(false);
+    }
+    let validator = RecValidator::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(validator.is_active())
+}
+
+/// Whether `key` is the oracle or registry PDA allowed to report certifications
+fn is_attestation_reporter(key: &Pubkey) -> bool {
+    let (oracle, _) = Pubkey::find_program_address(&[b"oracle_authority"], &ORACLE_PROGRAM_ID);
+    let (registry, _) = Pubkey::find_program_address(&[b"registry_authority"], &REGISTRY_PROGRAM_ID);
+    *key == oracle || *key == registry
+}
+
+/// The validator PDA a proposal's remaining accounts must start with
+fn rec_validator_target<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    validator: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    let [target, ..] = remaining_accounts else {
+        return err!(ErrorCode::InvalidProposalTarget);
+    };
+    let (expected, _) = Pubkey::find_program_address(&[b"rec_validator", validator.as_ref()], &crate::ID);
+    require_keys_eq!(target.key(), expected, ErrorCode::InvalidProposalTarget);
+    Ok(target)
+}
+
+/// Create a validator PDA from within `execute_proposal`, paid for by the executor
+fn create_rec_validator<'info>(
+    target: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    validator: RecValidator,
+) -> Result<()> {
+    require!(target.data_is_empty(), ErrorCode::ValidatorAlreadyAuthorized);
+    
+    let space = 8 + RecValidator::INIT_SPACE;
+    let (_, bump) = Pubkey::find_program_address(&[b"rec_validator", validator.pubkey.as_ref()], &crate::ID);
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: target.clone(),
+            },
+            &[&[b"rec_validator", validator.pubkey.as_ref(), &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )?;
+    
+    validator.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// Count distinct `members` that signed, either as `authority` or as remaining accounts
+fn approvals(members: &[Pubkey], authority: &Signer, co_signers: &[AccountInfo]) -> usize {
+    let mut signed: Vec<Pubkey> = co_signers
+        .iter()
+        .filter(|info| info.is_signer)
+        .map(|info| info.key())
+        .chain(std::iter::once(authority.key()))
+        .filter(|key| members.contains(key))
+        .collect();
+    signed.sort();
+    signed.dedup();
+    signed.len()
+}
+
+/// Record an attestation outcome and report the validator's resulting reliability
+fn apply_attestation(
+    poa_config: &mut PoAConfig,
+    validator: &mut RecValidator,
+    outcome: AttestationOutcome,
+) -> Result<()> {
+    let (reliability_bps, deactivated) = poa_config.record_attestation(validator, outcome)?;
+    
+    emit!(ValidatorReliabilityUpdated {
+        validator: validator.pubkey,
+        outcome,
+        reliability_bps,
+        deactivated,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Leading fields of registry's `MeterAccount`, up to the validator who activated it
+#[derive(AnchorDeserialize)]
+struct ActivatedMeter {
+    _meter_id: String,
+    _owner: Pubkey,
+    _meter_type: u8,
+    _rated_capacity_w: u64,
+    _installation_profile: u8,
+    _grid_node: Pubkey,
+    _status: u8,
+    _inspection_reference: String,
+    activated_by: Pubkey,
+}
+
+/// Count an overturned attestation against the validator who activated a meter whose
+/// reading dispute was upheld
+///
+/// Meters activated before attestations were counted have nothing to overturn, and a
+/// validator whose account has since been closed is passed as its empty PDA.
+fn overturn_meter_attestation<'info>(
+    meter: &AccountInfo<'info>,
+    poa_config: &mut PoAConfig,
+    rec_validator: Option<&UncheckedAccount<'info>>,
+) -> Result<()> {
+    require_keys_eq!(*meter.owner, REGISTRY_PROGRAM_ID, ErrorCode::InvalidDisputeTarget);
+    let activated_by = {
+        let data = meter.try_borrow_data()?;
+        let mut fields = data.get(8..).ok_or(ErrorCode::InvalidDisputeTarget)?;
+        ActivatedMeter::deserialize(&mut fields)?.activated_by
+    };
+    if activated_by == Pubkey::default() {
+        return Ok(());
+    }
+    
+    let info = rec_validator.ok_or(ErrorCode::InvalidDisputeTarget)?;
+    let (expected, _) = Pubkey::find_program_address(&[b"rec_validator", activated_by.as_ref()], &crate::ID);
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidDisputeTarget);
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidDisputeTarget);
+    let mut data = info.try_borrow_mut_data()?;
+    let mut validator = RecValidator::try_deserialize(&mut &data[..])?;
+    if validator.overturned_attestations < validator.attestations {
+        apply_attestation(poa_config, &mut validator, AttestationOutcome::Overturned)?;
+        validator.try_serialize(&mut &mut data[..])?;
+    }
+    Ok(())
+}
+
+/// Pay lamports out of a stake or dispute PDA, which governance owns and can debit directly
+fn pay_out_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+fn validate_multisig(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_AUTHORITY_SIGNERS,
+        ErrorCode::InvalidAuthorityConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        ErrorCode::InvalidAuthorityConfig
+    );
     
-    #[account(constraint = university_authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
-    pub university_authority: Signer<'info>,
+    let mut unique = signers.to_vec();
+    unique.sort();
+    unique.dedup();
+    require!(
+        unique.len() == signers.len() && !unique.contains(&Pubkey::default()),
+        ErrorCode::InvalidAuthorityConfig
+    );
+    Ok(())
 }
 
-#[derive(Accounts)]
-pub struct RemoveRecValidator<'info> {
-    #[account(mut, has_one = university_authority @ ErrorCode::UnauthorizedAuthority)]
-    pub poa_config: Account<'info, PoAConfig>,
-    
-    #[account(constraint = university_authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
-    pub university_authority: Signer<'info>,
+/// Emergency pause flags, read by every program before scoped operations
+#[account]
+#[derive(InitSpace)]
+pub struct PauseConfig {
+    pub guardian: Pubkey,            // Can pause but not unpause
+    pub paused_scopes: u8,           // Bitmask of `PauseScope::bit()`
+    pub updated_at: i64,
 }
 
-#[derive(Accounts)]
-pub struct UpdateMinValidators<'info> {
-    #[account(mut, has_one = university_authority @ ErrorCode::UnauthorizedAuthority)]
-    pub poa_config: Account<'info, PoAConfig>,
+impl PauseConfig {
+    /// Whether `scope` is paused, directly or through a global pause
+    pub fn is_paused(&self, scope: PauseScope) -> bool {
+        self.paused_scopes & (scope.bit() | PauseScope::Global.bit()) != 0
+    }
+}
+
+/// Protocol-wide parameters, read by the other programs as a typed account and changed only
+/// through governance actions. Every change bumps `version`.
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolParams {
+    pub version: u64,
+    pub params: ParamValues,
+    pub updated_at: i64,
+}
+
+impl ProtocolParams {
+    /// Change the values in place, bump the version and announce it
+    pub fn apply(&mut self, change: impl FnOnce(&mut ParamValues)) -> Result<()> {
+        let mut params = self.params;
+        change(&mut params);
+        require!(params.is_valid(), ErrorCode::InvalidProtocolParams);
+        
+        let now = Clock::get()?.unix_timestamp;
+        self.params = params;
+        self.version += 1;
+        self.updated_at = now;
+        
+        emit!(ProtocolParamsUpdated {
+            version: self.version,
+            params,
+            timestamp: now,
+        });
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ParamValues {
+    pub market_fee_bps: u16,
+    pub min_rec_validators: u8,
+    pub oracle_reporter: Pubkey,         // Key allowed to report readings to the oracle
+    pub epoch_duration_secs: i64,
+    pub min_clearing_interval_secs: i64,
+    pub max_staleness_secs: i64,
+    pub min_price_per_kwh: u64,          // Floor for tariff price bands
+    pub max_price_per_kwh: u64,          // Ceiling for tariff price bands, 0 = no ceiling
+}
+
+impl ParamValues {
+    pub fn is_valid(&self) -> bool {
+        self.market_fee_bps <= 10_000
+            && self.min_rec_validators > 0
+            && self.oracle_reporter != Pubkey::default()
+            && self.epoch_duration_secs > 0
+            && self.min_clearing_interval_secs >= 0
+            && self.max_staleness_secs > 0
+            && (self.max_price_per_kwh == 0 || self.min_price_per_kwh <= self.max_price_per_kwh)
+    }
     
-    #[account(constraint = university_authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
-    pub university_authority: Signer<'info>,
+    /// Whether a tariff's price band lies within the protocol limits
+    pub fn allows_price_band(&self, min_price: u64, max_price: u64) -> bool {
+        min_price >= self.min_price_per_kwh
+            && (self.max_price_per_kwh == 0 || max_price <= self.max_price_per_kwh)
+    }
 }
 
-#[derive(Accounts)]
-pub struct DeactivateRecValidator<'info> {
-    #[account(mut, has_one = university_authority @ ErrorCode::UnauthorizedAuthority)]
-    pub poa_config: Account<'info, PoAConfig>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PauseScope {
+    Global,
+    Trading,        // Order entry, matching and market clearing
+    Minting,        // Energy token minting
+    OracleIngest,   // Meter readings and weather observations
+}
+
+impl PauseScope {
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttestationOutcome {
+    Certified,      // Reported by the oracle when a validator reviews a reading
+    Missed,         // Reported by the university authority
+    Overturned,     // A certification proven false
+}
+
+/// Lamports a REC validator puts at risk for its certifications, held in this PDA
+#[account]
+#[derive(InitSpace)]
+pub struct ValidatorStake {
+    pub validator: Pubkey,
+    pub amount: u64,                   // Staked lamports, excluding rent
+    pub slashed_amount: u64,
+    pub slashed_certifications: u32,   // Overturned certifications already slashed for
+    pub updated_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalConfig {
+    pub proposal_count: u64,
+    pub voting_period_secs: i64,
+    pub timelock_delay_secs: i64,    // Wait between queueing and executing sensitive actions
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    pub status: ProposalStatus,
+    pub yes_votes: u8,
+    pub no_votes: u8,
+    pub quorum: u8,                  // Votes needed, a majority of active validators at creation
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub eta: i64,                    // Earliest execution time once queued
+    pub cancel_votes: u8,            // Validators voting to cancel during the timelock
+    pub executed_at: i64,
+}
+
+impl Proposal {
+    /// Quorum reached and more validators for than against
+    pub fn passed(&self) -> bool {
+        carried(self.yes_votes, self.no_votes, self.quorum)
+    }
     
-    #[account(constraint = university_authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
-    pub university_authority: Signer<'info>,
+    /// Queued and still inside its timelock, so it can be cancelled
+    pub fn is_pending(&self, now: i64) -> bool {
+        self.status == ProposalStatus::Queued && now < self.eta
+    }
 }
 
-#[derive(Accounts)]
-pub struct ReactivateRecValidator<'info> {
-    #[account(mut, has_one = university_authority @ ErrorCode::UnauthorizedAuthority)]
-    pub poa_config: Account<'info, PoAConfig>,
+/// Quorum reached and more votes for than against
+fn carried(yes_votes: u8, no_votes: u8, quorum: u8) -> bool {
+    yes_votes as u16 + no_votes as u16 >= quorum as u16 && yes_votes > no_votes
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,            // Or the dispute, for dispute votes
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub voted_at: i64,
+}
+
+/// Changes a proposal can make once it passes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ProposalAction {
+    /// Market fee in `ProtocolParams`, plus trading::set_clearing_enabled
+    UpdateMarketParams { market_fee_bps: u16, clearing_enabled: bool },
+    AddRecValidator {
+        validator: Pubkey,
+        #[max_len(MAX_VALIDATOR_NAME_LEN)]
+        department: String,
+    },
+    RemoveRecValidator { validator: Pubkey },
+    SetMinRecValidators { min_validators: u8 },
+    /// `ProtocolParams::oracle_reporter`, the key allowed to report readings
+    UpdateOracleGateway { api_gateway: Pubkey },
+    /// Replace every protocol parameter; programs read them from `ProtocolParams` when used
+    UpdateProtocolParams { params: ParamValues },
+    /// Upgrade a registered program from `buffer`, whose contents after the loader header
+    /// must hash (sha256) to `buffer_hash`
+    UpgradeProgram { program: Pubkey, buffer: Pubkey, buffer_hash: [u8; 32] },
+    /// Suspend a validator without removing it, until `ReactivateRecValidator`
+    DeactivateRecValidator { validator: Pubkey },
+    /// Count a certification `validator` failed to make; may deactivate it
+    ReportMissedAttestation { validator: Pubkey },
+    /// Reliability below which validators are deactivated, 0 disables it
+    SetMinReliability { min_reliability_bps: u16 },
+    ReactivateRecValidator { validator: Pubkey },
+}
+
+impl ProposalAction {
+    /// Fits the space reserved for it
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ProposalAction::AddRecValidator { department, .. } => department.len() <= MAX_VALIDATOR_NAME_LEN,
+            ProposalAction::UpdateProtocolParams { params } => params.is_valid(),
+            ProposalAction::SetMinReliability { min_reliability_bps } => *min_reliability_bps <= 10_000,
+            _ => true,
+        }
+    }
     
-    #[account(constraint = university_authority.key() == UNIVERSITY_AUTHORITY_PUBKEY)]
-    pub university_authority: Signer<'info>,
+    /// Actions a binding referendum may carry. Validators, the reading reporter, protocol
+    /// parameters and upgrades need REC validator approval, so only proposals carry them.
+    pub fn is_community_action(&self) -> bool {
+        matches!(self, ProposalAction::UpdateMarketParams { .. })
+    }
+    
+    /// Actions that can hurt users or weaken oversight wait out the timelock
+    pub fn is_sensitive(&self) -> bool {
+        !matches!(self, ProposalAction::AddRecValidator { .. })
+    }
 }
 
-#[derive(Accounts)]
-pub struct GetValidatorInfo<'info> {
-    pub poa_config: Account<'info, PoAConfig>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ProposalStatus {
+    Voting,
+    Queued,
+    Executed,
+    Defeated,
+    Cancelled,
 }
 
-#[derive(Accounts)]
-pub struct IsAuthorizedRecValidator<'info> {
-    pub poa_config: Account<'info, PoAConfig>,
+/// A program upgradeable only through governance, with the hashes it has been deployed at
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramRecord {
+    pub program: Pubkey,
+    pub upgrade_count: u64,
+    #[max_len(MAX_DEPLOY_HISTORY)]
+    pub deployments: Vec<Deployment>,    // Oldest first
 }
 
+impl ProgramRecord {
+    pub fn record_deployment(&mut self, program_hash: [u8; 32], now: i64) {
+        if self.deployments.len() == MAX_DEPLOY_HISTORY {
+            self.deployments.remove(0);
+        }
+        self.deployments.push(Deployment { program_hash, deployed_at: now });
+    }
+}
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Deployment {
+    pub program_hash: [u8; 32],          // sha256 of the program binary
+    pub deployed_at: i64,
+}
 
-// Data structs
 #[account]
-pub struct PoAConfig {
-    pub university_authority: Pubkey,
-    pub authorized_rec_validators: Vec<RecValidatorInfo>,
-    pub min_rec_validators: u8,
-    pub emergency_paused: bool,
+#[derive(InitSpace)]
+pub struct DisputeConfig {
+    pub dispute_count: u64,
+    pub bond_lamports: u64,          // Posted by the claimant when opening a dispute
+    pub voting_period_secs: i64,
+    pub treasury: Pubkey,            // Receives the bonds of rejected disputes
+}
+
+/// A challenge to a trade or meter reading, decided by REC validator vote
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub id: u64,
+    pub claimant: Pubkey,
+    pub subject: DisputeSubject,
+    #[max_len(MAX_EVIDENCE_URIS, MAX_EVIDENCE_URI_LEN)]
+    pub evidence_uris: Vec<String>,
+    pub bond: u64,                   // Lamports held in this account on top of rent
+    pub status: DisputeStatus,
+    pub uphold_votes: u8,
+    pub reject_votes: u8,
+    pub quorum: u8,                  // Votes needed, a majority of active validators when opened
     pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub resolved_at: i64,
 }
 
-impl PoAConfig {
-    pub const MAX_REC_VALIDATORS: usize = 10;
-    pub const LEN: usize = 32 + 4 + (RecValidatorInfo::LEN * Self::MAX_REC_VALIDATORS) + 1 + 1 + 8;
+impl Dispute {
+    /// Quorum reached and more validators upholding than rejecting
+    pub fn upheld(&self) -> bool {
+        carried(self.uphold_votes, self.reject_votes, self.quorum)
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct RecValidatorInfo {
-    pub pubkey: Pubkey,
-    pub authority_name: String, // e.g., "University Sustainability Office"
-    pub certification_authority: bool,
-    pub active: bool,
-    pub added_at: i64,
+/// What a dispute challenges, with the remedy applied if it is upheld
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub enum DisputeSubject {
+    /// Seller under-delivered; trading::refund_trade refunds the buyer
+    Trade { trade_record: Pubkey, refund_amount: u64 },
+    /// Wrong reading; oracle::correct_meter_reading adjusts the meter's totals
+    Reading {
+        meter: Pubkey,
+        reading_timestamp: i64,
+        generation_delta: i64,
+        consumption_delta: i64,
+    },
+}
+
+impl DisputeSubject {
+    pub fn has_remedy(&self) -> bool {
+        match self {
+            DisputeSubject::Trade { refund_amount, .. } => *refund_amount > 0,
+            DisputeSubject::Reading { generation_delta, consumption_delta, .. } => {
+                *generation_delta != 0 || *consumption_delta != 0
+            }
+        }
+    }
+    
+    /// The trade record or meter account the remedy changes
+    pub fn target(&self) -> Pubkey {
+        match self {
+            DisputeSubject::Trade { trade_record, .. } => *trade_record,
+            DisputeSubject::Reading { meter, .. } => *meter,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DisputeStatus {
+    Voting,
+    Upheld,
+    Rejected,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReferendumConfig {
+    pub token_mint: Pubkey,          // Energy token that carries voting weight
+    pub referendum_count: u64,
+    pub voting_period_secs: i64,
+    pub min_turnout: u64,            // Token base units that must vote for a referendum to pass
+}
+
+/// Energy tokens a holder has locked in the referendum vault to vote with
+///
+/// Weight is what was locked before a referendum opened. The amount before the latest
+/// deposit is kept too, so one top-up while a referendum is open does not void the
+/// earlier tokens; after two such top-ups the lock has no weight in it.
+#[account]
+#[derive(InitSpace)]
+pub struct TokenLock {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub locked_at: i64,              // Time of the latest deposit
+    pub prior_amount: u64,           // Amount before the latest deposit
+    pub prior_locked_at: i64,
+    pub unlock_at: i64,              // End of the last referendum voted in
+}
+
+impl TokenLock {
+    pub fn deposit(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.locked_at < now {
+            self.prior_amount = self.amount;
+            self.prior_locked_at = self.locked_at;
+        }
+        self.amount = self.amount.checked_add(amount).ok_or(ErrorCode::InvalidLockAmount)?;
+        self.locked_at = now;
+        Ok(())
+    }
+    
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        self.amount = self.amount.checked_sub(amount).ok_or(ErrorCode::InvalidLockAmount)?;
+        self.prior_amount = self.prior_amount.min(self.amount);
+        Ok(())
+    }
+    
+    /// Voting weight in a referendum opened at `snapshot_at`
+    pub fn weight_at(&self, snapshot_at: i64) -> u64 {
+        if self.locked_at < snapshot_at {
+            self.amount
+        } else if self.prior_locked_at < snapshot_at {
+            self.prior_amount
+        } else {
+            0
+        }
+    }
+}
+
+/// A question put to energy token holders
+#[account]
+#[derive(InitSpace)]
+pub struct Referendum {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub kind: ReferendumKind,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub question: String,
+    pub status: ReferendumStatus,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub voter_count: u32,
+    pub min_turnout: u64,            // From the config at creation
+    pub snapshot_at: i64,            // Only tokens locked before this vote
+    pub voting_ends_at: i64,
+    pub eta: i64,                    // Earliest execution time of a passed binding referendum
+    pub executed_at: i64,
+}
+
+impl Referendum {
+    /// Enough tokens voted and more for than against
+    pub fn passed(&self) -> bool {
+        self.yes_weight.saturating_add(self.no_weight) >= self.min_turnout
+            && self.yes_weight > self.no_weight
+    }
+    
+    pub fn is_binding(&self) -> bool {
+        matches!(self.kind, ReferendumKind::Binding { .. })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ReferendumKind {
+    /// Non-binding, e.g. how to allocate the sustainability fund
+    Advisory,
+    /// Executes `action` if passed
+    Binding { action: ProposalAction },
 }
 
-impl RecValidatorInfo {
-    pub const LEN: usize = 32 + 64 + 1 + 1 + 8; // pubkey + authority_name + certification_authority + active + added_at
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ReferendumStatus {
+    Voting,
+    Passed,
+    Rejected,
+    Executed,
+    Cancelled,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReferendumBallot {
+    pub referendum: Pubkey,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+    pub voted_at: i64,
 }
 
 // Events
 #[event]
 pub struct EmergencyPauseActivated {
     pub authority: Pubkey,
+    pub scope: PauseScope,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct EmergencyPauseDeactivated {
     pub authority: Pubkey,
+    pub scope: PauseScope,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseGuardianUpdated {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct PoAInitialized {
     pub authority: Pubkey,
-    pub validator_count: u8,
+    pub validator_count: u32,
     pub min_validators: u8,
     pub timestamp: i64,
 }
@@ -480,6 +3016,12 @@ pub struct RecValidatorRemoved {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct RecValidatorClosed {
+    pub validator: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MinValidatorsUpdated {
     pub old_min: u8,
@@ -488,6 +3030,13 @@ pub struct MinValidatorsUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ProtocolParamsUpdated {
+    pub version: u64,
+    pub params: ParamValues,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct RecValidatorDeactivated {
     pub validator: Pubkey,
@@ -502,6 +3051,228 @@ pub struct RecValidatorReactivated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ValidatorReliabilityUpdated {
+    pub validator: Pubkey,
+    pub outcome: AttestationOutcome,
+    pub reliability_bps: u16,
+    pub deactivated: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MinReliabilityUpdated {
+    pub old_min_bps: u16,
+    pub new_min_bps: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ValidatorStakeDeposited {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub total_stake: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ValidatorStakeWithdrawn {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub total_stake: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ValidatorSlashed {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityRotationProposed {
+    pub proposed_by: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityRotated {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalQueued {
+    pub proposal_id: u64,
+    pub action: ProposalAction,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CancelVoteCast {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub cancel_votes: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal_id: u64,
+    pub cancelled_by: Pubkey,        // University authority, or the validator casting the deciding vote
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimelockDelayUpdated {
+    pub old_delay_secs: i64,
+    pub new_delay_secs: i64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalDefeated {
+    pub proposal_id: u64,
+    pub yes_votes: u8,
+    pub no_votes: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramRegistered {
+    pub program: Pubkey,
+    pub program_hash: [u8; 32],
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramUpgraded {
+    pub program: Pubkey,
+    pub buffer: Pubkey,
+    pub program_hash: [u8; 32],
+    pub upgrade_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub dispute_id: u64,
+    pub claimant: Pubkey,
+    pub subject: DisputeSubject,
+    pub bond: u64,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeVoteCast {
+    pub dispute_id: u64,
+    pub voter: Pubkey,
+    pub uphold: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub dispute_id: u64,
+    pub upheld: bool,
+    pub uphold_votes: u8,
+    pub reject_votes: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensLocked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_locked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensUnlocked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_locked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferendumCreated {
+    pub referendum_id: u64,
+    pub proposer: Pubkey,
+    pub binding: bool,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferendumVoteCast {
+    pub referendum_id: u64,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferendumFinalized {
+    pub referendum_id: u64,
+    pub passed: bool,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub voter_count: u32,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferendumExecuted {
+    pub referendum_id: u64,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferendumCancelled {
+    pub referendum_id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
 // Errors
 #[error_code]
 pub enum ErrorCode {
@@ -523,4 +3294,86 @@ pub enum ErrorCode {
     ValidatorAlreadyInactive,
     #[msg("Validator already active")]
     ValidatorAlreadyActive,
+    #[msg("Signer is not an active REC validator")]
+    UnauthorizedValidator,
+    #[msg("Voting period out of range")]
+    InvalidVotingPeriod,
+    #[msg("Invalid proposal")]
+    InvalidProposal,
+    #[msg("Proposal is not open for voting")]
+    ProposalNotActive,
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    #[msg("Voting period has not ended")]
+    VotingStillOpen,
+    #[msg("Proposal target accounts do not match its action")]
+    InvalidProposalTarget,
+    #[msg("Timelock delay out of range")]
+    InvalidTimelockDelay,
+    #[msg("Proposal is not queued")]
+    ProposalNotQueued,
+    #[msg("Proposal is not queued or its timelock has passed")]
+    ProposalNotCancellable,
+    #[msg("Timelock has not expired")]
+    TimelockNotExpired,
+    #[msg("Not enough authority multisig members signed")]
+    InsufficientAuthoritySignatures,
+    #[msg("Authority multisig needs 1 to 7 unique keys and a threshold between 1 and the key count")]
+    InvalidAuthorityConfig,
+    #[msg("No authority rotation is pending")]
+    NoPendingAuthority,
+    #[msg("Governance is globally paused")]
+    GovernancePaused,
+    #[msg("Validator department name is too long")]
+    InvalidValidatorName,
+    #[msg("Attestation report does not apply to this validator")]
+    InvalidAttestationReport,
+    #[msg("Reliability threshold must be at most 10000 bps")]
+    InvalidReliabilityThreshold,
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+    #[msg("Validator must be inactive to withdraw stake")]
+    ValidatorStillActive,
+    #[msg("Validator has no unslashed overturned certification")]
+    NoSlashableFault,
+    #[msg("Stake is still unbonding after the validator was deactivated")]
+    StakeUnbonding,
+    #[msg("Overturned certifications must be slashed before stake is withdrawn")]
+    UnslashedFault,
+    #[msg("Validator stake must be withdrawn before its account is closed")]
+    StakeNotSettled,
+    #[msg("Validator has been removed")]
+    ValidatorRemoved,
+    #[msg("Only a removed validator's account can be closed")]
+    ValidatorNotRemoved,
+    #[msg("Certifications are only reported by the oracle or registry")]
+    UnauthorizedAttestationReporter,
+    #[msg("Dispute needs 1 to 4 evidence URIs of at most 128 bytes")]
+    InvalidEvidence,
+    #[msg("Dispute must request a non-zero refund or correction")]
+    InvalidDisputeRemedy,
+    #[msg("Dispute is not open for voting")]
+    DisputeNotActive,
+    #[msg("Dispute accounts do not match its subject")]
+    InvalidDisputeTarget,
+    #[msg("Invalid token lock amount")]
+    InvalidLockAmount,
+    #[msg("Tokens are locked until the referenda they voted in close")]
+    TokensStillLocked,
+    #[msg("No tokens were locked before this referendum opened")]
+    NoVotingWeight,
+    #[msg("Referendum is not open for voting")]
+    ReferendumNotActive,
+    #[msg("Referendum is not a passed binding referendum awaiting execution")]
+    ReferendumNotExecutable,
+    #[msg("Invalid protocol parameters")]
+    InvalidProtocolParams,
+    #[msg("Governance is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+    #[msg("Upgrade buffer is not a buffer owned by the governance authority")]
+    InvalidUpgradeBuffer,
+    #[msg("Upgrade buffer does not match the approved hash")]
+    UpgradeHashMismatch,
+    #[msg("Validators are added through proposals once proposals are set up")]
+    BootstrapEnded,
 }
\ No newline at end of file
@@ -1,21 +1,64 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use governance::program::Governance as GovernanceProgram;
+use governance::{ParamValues, PauseConfig, PauseScope, PoAConfig, ProtocolParams, RecValidator};
+use registry::program::Registry as RegistryProgram;
+use registry::{InstallationProfile, MeterAccount, MeterType};
+use trading::program::Trading as TradingProgram;
+use trading::{EpochAuction, Market};
 
 declare_id!("7sA8No5jojLboTzQQTU3fiAL8kGAjTzPgXtaMEYNKPEC");
 
+/// Interval assumed for a meter's first reading, when there is no previous timestamp (15 minutes)
+pub const DEFAULT_READING_INTERVAL_SECS: i64 = 900;
+
+/// How far a reported timestamp may run ahead of the cluster clock (5 minutes)
+pub const MAX_CLOCK_DRIFT_SECS: i64 = 300;
+
+/// Headroom allowed above rated capacity × interval before a reading is quarantined (10%)
+pub const CAPACITY_TOLERANCE_BPS: u128 = 1_000;
+
+/// Length of one campus weather observation interval (15 minutes)
+pub const WEATHER_INTERVAL_SECS: i64 = 900;
+
+/// Share of nameplate output a solar installation delivers at standard irradiance (85%)
+pub const SOLAR_PERFORMANCE_RATIO_BPS: u128 = 8_500;
+
+/// Output lost per °C of ambient temperature above 25 °C (0.4%)
+pub const SOLAR_TEMP_DERATE_BPS_PER_C: i64 = 40;
+
+/// Relative deviation from expected solar yield that gets a reading flagged (50%)
+pub const SOLAR_DEVIATION_TOLERANCE_BPS: u128 = 5_000;
+
+/// Absolute slack on top of the relative tolerance, as a share of rated energy (2%)
+pub const SOLAR_DEVIATION_FLOOR_BPS: u128 = 200;
+
+/// Maximum number of time-of-use periods in a tariff schedule
+pub const MAX_TARIFF_PERIODS: usize = 8;
+
 #[program]
 pub mod oracle {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, api_gateway: Pubkey) -> Result<()> {
+    /// Set up oracle state; the gateway key and the epoch, clearing and staleness windows
+    /// are read from the governance `ProtocolParams` whenever they are used
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let oracle_data = &mut ctx.accounts.oracle_data;
         oracle_data.authority = ctx.accounts.authority.key();
-        oracle_data.api_gateway = api_gateway;
         oracle_data.total_readings = 0;
         oracle_data.last_clearing = 0;
         oracle_data.active = true;
         oracle_data.created_at = Clock::get()?.unix_timestamp;
+        oracle_data.total_batches = 0;
+        oracle_data.clearing_epoch = 0;
+        oracle_data.clearing_in_progress = false;
+        oracle_data.frozen_until = 0;
+        oracle_data.degraded = false;
+        oracle_data.degraded_since = 0;
+        oracle_data.flagged_readings = 0;
+        oracle_data.last_ingest_at = oracle_data.created_at;
         
-        msg!("Oracle program initialized with API Gateway: {}", api_gateway);
+        msg!("Oracle program initialized");
         Ok(())
     }
 
@@ -33,12 +76,66 @@ pub mod oracle {
         
         // Only API Gateway can submit meter readings
         require!(
-            ctx.accounts.authority.key() == oracle_data.api_gateway,
+            ctx.accounts.authority.key() == ctx.accounts.protocol_params.params.oracle_reporter,
             ErrorCode::UnauthorizedGateway
         );
         
-        oracle_data.total_readings += 1;
-        oracle_data.last_reading_timestamp = reading_timestamp;
+        // Epochs that are clearing or settled only see the dataset they were frozen with
+        require!(
+            reading_timestamp >= oracle_data.frozen_until,
+            ErrorCode::EpochFrozen
+        );
+        require_not_future(reading_timestamp, Clock::get()?.unix_timestamp)?;
+        
+        // Readings above the meter's physical limits must go through quarantine
+        let meter_account = &ctx.accounts.meter_account;
+        let interval_secs = reading_interval(
+            meter_account,
+            reading_timestamp,
+            ctx.accounts.protocol_params.params.epoch_duration_secs,
+        )?;
+        let assessment = assess_reading(
+            meter_account,
+            energy_produced,
+            energy_consumed,
+            interval_secs,
+        )?;
+        require!(
+            assessment == ReadingAssessment::Plausible,
+            ErrorCode::ReadingExceedsCapacity
+        );
+        
+        // Solar generation that doesn't match the campus weather feed must go through quarantine
+        require!(
+            solar_deviation(
+                meter_account,
+                energy_produced,
+                interval_secs,
+                &ctx.accounts.weather_observation,
+            )?
+            .is_none(),
+            ErrorCode::SolarYieldDeviates
+        );
+        
+        oracle_data.total_readings = oracle_data
+            .total_readings
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        oracle_data.last_reading_timestamp = oracle_data.last_reading_timestamp.max(reading_timestamp);
+        let now = Clock::get()?.unix_timestamp;
+        oracle_data.last_ingest_at = now;
+        update_liveness(oracle_data, &ctx.accounts.protocol_params.params, now);
+        
+        post_reading_to_registry(
+            &ctx.accounts.registry_program,
+            &ctx.accounts.meter_account,
+            &ctx.accounts.pause_config,
+            &ctx.accounts.oracle_authority,
+            ctx.bumps.oracle_authority,
+            energy_produced,
+            energy_consumed,
+            reading_timestamp,
+        )?;
         
         emit!(MeterReadingSubmitted {
             meter_id: meter_id.clone(),
@@ -55,27 +152,497 @@ pub mod oracle {
         Ok(())
     }
 
-    /// Trigger market clearing process (only via API Gateway)
-    pub fn trigger_market_clearing(ctx: Context<TriggerMarketClearing>) -> Result<()> {
+    /// Record the campus irradiance and temperature for one weather interval (only via API Gateway)
+    pub fn submit_weather_observation(
+        ctx: Context<SubmitWeatherObservation>,
+        interval_start: i64,
+        irradiance_w_m2: u32,
+        temperature_centi_c: i32,
+    ) -> Result<()> {
+        let oracle_data = &ctx.accounts.oracle_data;
+        
+        require!(oracle_data.active, ErrorCode::OracleInactive);
+        
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_params.params.oracle_reporter,
+            ErrorCode::UnauthorizedGateway
+        );
+        
+        // Above-atmosphere irradiance is ~1361 W/m²; campus temperatures stay within -50..70 °C
+        require!(
+            interval_start.rem_euclid(WEATHER_INTERVAL_SECS) == 0
+                && irradiance_w_m2 <= 1_500
+                && (-5_000..=7_000).contains(&temperature_centi_c),
+            ErrorCode::InvalidWeatherObservation
+        );
+        
+        let weather = &mut ctx.accounts.weather_observation;
+        weather.interval_start = interval_start;
+        weather.irradiance_w_m2 = irradiance_w_m2;
+        weather.temperature_centi_c = temperature_centi_c;
+        weather.submitter = ctx.accounts.authority.key();
+        weather.recorded_at = Clock::get()?.unix_timestamp;
+        
+        emit!(WeatherObservationRecorded {
+            interval_start,
+            irradiance_w_m2,
+            temperature_centi_c,
+            timestamp: weather.recorded_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Park a reading that exceeds the meter's rated capacity, or a solar reading that
+    /// deviates from the recorded weather, for validator review
+    pub fn quarantine_meter_reading(
+        ctx: Context<QuarantineMeterReading>,
+        meter_id: String,
+        energy_produced: u64,
+        energy_consumed: u64,
+        reading_timestamp: i64,
+    ) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        
+        require!(oracle_data.active, ErrorCode::OracleInactive);
+        
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_params.params.oracle_reporter,
+            ErrorCode::UnauthorizedGateway
+        );
+        
+        require_not_future(reading_timestamp, Clock::get()?.unix_timestamp)?;
+        
+        // Impossible readings are rejected outright; only over-capacity or weather-deviating
+        // ones can be reviewed
+        let meter_account = &ctx.accounts.meter_account;
+        let interval_secs = reading_interval(
+            meter_account,
+            reading_timestamp,
+            ctx.accounts.protocol_params.params.epoch_duration_secs,
+        )?;
+        let assessment = assess_reading(
+            meter_account,
+            energy_produced,
+            energy_consumed,
+            interval_secs,
+        )?;
+        let deviation = solar_deviation(
+            meter_account,
+            energy_produced,
+            interval_secs,
+            &ctx.accounts.weather_observation,
+        )?;
+        require!(
+            assessment == ReadingAssessment::ExceedsCapacity || deviation.is_some(),
+            ErrorCode::ReadingNotQuarantinable
+        );
+        
+        if let Some((expected_wh, weather)) = deviation {
+            oracle_data.flagged_readings = oracle_data
+                .flagged_readings
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            
+            emit!(SolarReadingFlagged {
+                meter_id: meter_id.clone(),
+                energy_produced,
+                expected_wh,
+                irradiance_w_m2: weather.irradiance_w_m2,
+                temperature_centi_c: weather.temperature_centi_c,
+                timestamp: reading_timestamp,
+            });
+        }
+        
+        let quarantined = &mut ctx.accounts.quarantined_reading;
+        quarantined.meter_id = meter_id.clone();
+        quarantined.energy_produced = energy_produced;
+        quarantined.energy_consumed = energy_consumed;
+        quarantined.reading_timestamp = reading_timestamp;
+        quarantined.submitter = ctx.accounts.authority.key();
+        quarantined.quarantined_at = Clock::get()?.unix_timestamp;
+        
+        emit!(ReadingQuarantined {
+            meter_id,
+            energy_produced,
+            energy_consumed,
+            rated_capacity_w: meter_account.rated_capacity_w,
+            timestamp: reading_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Approve or reject a quarantined reading (REC validators only)
+    pub fn review_quarantined_reading(
+        ctx: Context<ReviewQuarantinedReading>,
+        approve: bool,
+    ) -> Result<()> {
+        let quarantined = &ctx.accounts.quarantined_reading;
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        
+        if approve {
+            require!(
+                quarantined.reading_timestamp >= oracle_data.frozen_until,
+                ErrorCode::EpochFrozen
+            );
+            
+            oracle_data.total_readings = oracle_data
+                .total_readings
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            oracle_data.last_reading_timestamp = oracle_data
+                .last_reading_timestamp
+                .max(quarantined.reading_timestamp);
+            let now = Clock::get()?.unix_timestamp;
+            oracle_data.last_ingest_at = now;
+            update_liveness(oracle_data, &ctx.accounts.protocol_params.params, now);
+            
+            post_reading_to_registry(
+                &ctx.accounts.registry_program,
+                &ctx.accounts.meter_account,
+                &ctx.accounts.pause_config,
+                &ctx.accounts.oracle_authority,
+                ctx.bumps.oracle_authority,
+                quarantined.energy_produced,
+                quarantined.energy_consumed,
+                quarantined.reading_timestamp,
+            )?;
+            
+            emit!(MeterReadingSubmitted {
+                meter_id: quarantined.meter_id.clone(),
+                energy_produced: quarantined.energy_produced,
+                energy_consumed: quarantined.energy_consumed,
+                timestamp: quarantined.reading_timestamp,
+                submitter: quarantined.submitter,
+            });
+        }
+        
+        // Approving or rejecting, the review counts toward the validator's reliability
+        let signer_seeds: &[&[&[u8]]] = &[&[b"oracle_authority", &[ctx.bumps.oracle_authority]]];
+        governance::cpi::record_attestation(
+            CpiContext::new_with_signer(
+                ctx.accounts.governance_program.to_account_info(),
+                governance::cpi::accounts::RecordAttestation {
+                    poa_config: ctx.accounts.poa_config.to_account_info(),
+                    rec_validator: ctx.accounts.rec_validator.to_account_info(),
+                    reporter: ctx.accounts.oracle_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.accounts.validator.key(),
+        )?;
+        
+        emit!(QuarantinedReadingReviewed {
+            meter_id: quarantined.meter_id.clone(),
+            validator: ctx.accounts.validator.key(),
+            approved: approve,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Commit a Merkle root over a batch of readings for one interval (only via API Gateway)
+    pub fn commit_reading_batch(
+        ctx: Context<CommitReadingBatch>,
+        interval_start: i64,
+        interval_secs: u32,
+        reading_count: u32,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        
+        require!(oracle_data.active, ErrorCode::OracleInactive);
+        
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_params.params.oracle_reporter,
+            ErrorCode::UnauthorizedGateway
+        );
+        
+        require!(
+            interval_secs > 0 && reading_count > 0,
+            ErrorCode::InvalidReadingBatch
+        );
+        
+        require!(
+            interval_start >= oracle_data.frozen_until,
+            ErrorCode::EpochFrozen
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let batch_id = oracle_data.total_batches;
+        let interval_end = interval_start
+            .checked_add(interval_secs as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require_not_future(interval_end, now)?;
+        
+        let reading_batch = &mut ctx.accounts.reading_batch;
+        reading_batch.batch_id = batch_id;
+        reading_batch.merkle_root = merkle_root;
+        reading_batch.interval_start = interval_start;
+        reading_batch.interval_secs = interval_secs;
+        reading_batch.reading_count = reading_count;
+        reading_batch.submitter = ctx.accounts.authority.key();
+        reading_batch.committed_at = now;
+        
+        oracle_data.total_batches = oracle_data
+            .total_batches
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        oracle_data.total_readings = oracle_data
+            .total_readings
+            .checked_add(reading_count as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        oracle_data.last_reading_timestamp = oracle_data.last_reading_timestamp.max(interval_end);
+        oracle_data.last_ingest_at = reading_batch.committed_at;
+        update_liveness(oracle_data, &ctx.accounts.protocol_params.params, reading_batch.committed_at);
+        
+        emit!(ReadingBatchCommitted {
+            batch: reading_batch.key(),
+            batch_id,
+            merkle_root,
+            interval_start,
+            interval_secs,
+            reading_count,
+            timestamp: reading_batch.committed_at,
+        });
+        
+        Ok(())
+    }
+    
+    /// Prove that a single reading is included in a committed batch (permissionless)
+    pub fn verify_reading_inclusion(
+        ctx: Context<VerifyReadingInclusion>,
+        meter_id: String,
+        energy_produced: u64,
+        energy_consumed: u64,
+        reading_timestamp: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let reading_batch = &ctx.accounts.reading_batch;
+        
+        require!(
+            reading_batch.covers(reading_timestamp)?,
+            ErrorCode::ReadingOutsideBatch
+        );
+        
+        let leaf = reading_leaf(&meter_id, energy_produced, energy_consumed, reading_timestamp);
+        require!(
+            verify_reading_proof(&proof, reading_batch.merkle_root, leaf),
+            ErrorCode::InvalidMerkleProof
+        );
+        
+        emit!(ReadingInclusionVerified {
+            batch: reading_batch.key(),
+            meter_id,
+            energy_produced,
+            energy_consumed,
+            timestamp: reading_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Start clearing an elapsed epoch and run its auction on the trading program (only via API Gateway)
+    ///
+    /// Moves the epoch from Open to Clearing: every reading timestamped before the
+    /// end of the epoch is frozen from here on. Open orders are forwarded to the
+    /// auction as remaining accounts.
+    pub fn trigger_market_clearing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TriggerMarketClearing<'info>>,
+        epoch: u64,
+    ) -> Result<()> {
+        let params = &ctx.accounts.protocol_params.params;
         let oracle_data = &mut ctx.accounts.oracle_data;
         
         require!(oracle_data.active, ErrorCode::OracleInactive);
         
         // Only API Gateway can trigger market clearing
         require!(
-            ctx.accounts.authority.key() == oracle_data.api_gateway,
+            ctx.accounts.authority.key() == params.oracle_reporter,
             ErrorCode::UnauthorizedGateway
         );
         
+        require!(
+            !oracle_data.clearing_in_progress,
+            ErrorCode::MarketClearingInProgress
+        );
+        
+        // Never clear on stale data, even if nobody has called check_liveness yet
         let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            !oracle_data.degraded && !oracle_data.is_stale(current_time, params.max_staleness_secs),
+            ErrorCode::OracleDegraded
+        );
+        
+        require!(
+            oracle_data.last_clearing == 0
+                || current_time - oracle_data.last_clearing >= params.min_clearing_interval_secs,
+            ErrorCode::ClearingTooFrequent
+        );
+        
+        let (epoch_start, epoch_end) = epoch_bounds(epoch, params.epoch_duration_secs)?;
+        require!(epoch_end <= current_time, ErrorCode::EpochNotEnded);
+        require!(epoch_start >= oracle_data.frozen_until, ErrorCode::EpochFrozen);
+        
+        oracle_data.clearing_epoch = epoch;
+        oracle_data.clearing_in_progress = true;
+        oracle_data.frozen_until = epoch_end;
         oracle_data.last_clearing = current_time;
         
+        let market_epoch = &mut ctx.accounts.market_epoch;
+        market_epoch.epoch = epoch;
+        market_epoch.epoch_start = epoch_start;
+        market_epoch.epoch_end = epoch_end;
+        market_epoch.status = EpochStatus::Clearing;
+        market_epoch.clearing_price = 0;
+        market_epoch.cleared_volume = 0;
+        market_epoch.clearing_started_at = current_time;
+        market_epoch.settled_at = 0;
+        
+        let signer_seeds: &[&[&[u8]]] = &[&[b"oracle_authority", &[ctx.bumps.oracle_authority]]];
+        
+        let cpi_accounts = trading::cpi::accounts::RunEpochAuction {
+            market: ctx.accounts.market.to_account_info(),
+            epoch_auction: ctx.accounts.epoch_auction.to_account_info(),
+            protocol_params: ctx.accounts.protocol_params.to_account_info(),
+            clearing_authority: ctx.accounts.oracle_authority.to_account_info(),
+            payer: ctx.accounts.authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.trading_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        
+        trading::cpi::run_epoch_auction(cpi_ctx, epoch)?;
+        
         emit!(MarketClearingTriggered {
             authority: ctx.accounts.authority.key(),
+            epoch,
+            frozen_until: epoch_end,
+            timestamp: current_time,
+        });
+        
+        msg!("Market clearing triggered via API Gateway for epoch {} at timestamp: {}", epoch, current_time);
+        Ok(())
+    }
+    
+    /// Mark the clearing epoch as Settled once its auction result is recorded (only via API Gateway)
+    pub fn settle_market_clearing(ctx: Context<SettleMarketClearing>, epoch: u64) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_params.params.oracle_reporter,
+            ErrorCode::UnauthorizedGateway
+        );
+        
+        require!(
+            oracle_data.clearing_in_progress && oracle_data.clearing_epoch == epoch,
+            ErrorCode::EpochNotClearing
+        );
+        
+        let epoch_auction = &ctx.accounts.epoch_auction;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        let market_epoch = &mut ctx.accounts.market_epoch;
+        market_epoch.status = EpochStatus::Settled;
+        market_epoch.clearing_price = epoch_auction.clearing_price;
+        market_epoch.cleared_volume = epoch_auction.cleared_volume;
+        market_epoch.settled_at = current_time;
+        
+        oracle_data.clearing_in_progress = false;
+        
+        emit!(MarketEpochSettled {
+            epoch,
+            clearing_price: epoch_auction.clearing_price,
+            cleared_volume: epoch_auction.cleared_volume,
             timestamp: current_time,
         });
         
-        msg!("Market clearing triggered via API Gateway at timestamp: {}", current_time);
+        Ok(())
+    }
+    
+    /// Mark the oracle degraded when readings have gone stale, or live again once they resume (permissionless)
+    pub fn check_liveness(ctx: Context<CheckLiveness>) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        update_liveness(oracle_data, &ctx.accounts.protocol_params.params, Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+    
+    /// Publish the first grid tariff schedule (only via API Gateway)
+    pub fn initialize_tariff_schedule(
+        ctx: Context<InitializeTariffSchedule>,
+        periods: Vec<TariffPeriod>,
+        utc_offset_secs: i32,
+        effective_from: i64,
+        effective_until: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_params.params.oracle_reporter,
+            ErrorCode::UnauthorizedGateway
+        );
+        
+        let tariff_schedule = &mut ctx.accounts.tariff_schedule;
+        tariff_schedule.set(periods, utc_offset_secs, effective_from, effective_until)?;
+        
+        push_price_bands(
+            &ctx.accounts.trading_program,
+            &ctx.accounts.market,
+            &ctx.accounts.oracle_authority,
+            ctx.bumps.oracle_authority,
+            &ctx.accounts.protocol_params,
+            tariff_schedule,
+        )?;
+        
+        emit!(TariffScheduleUpdated {
+            authority: ctx.accounts.authority.key(),
+            period_count: tariff_schedule.periods.len() as u8,
+            effective_from,
+            effective_until,
+            timestamp: tariff_schedule.updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    /// Replace the grid tariff schedule and republish trading price bands (only via API Gateway)
+    pub fn update_tariff_schedule(
+        ctx: Context<UpdateTariffSchedule>,
+        periods: Vec<TariffPeriod>,
+        utc_offset_secs: i32,
+        effective_from: i64,
+        effective_until: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_params.params.oracle_reporter,
+            ErrorCode::UnauthorizedGateway
+        );
+        
+        let tariff_schedule = &mut ctx.accounts.tariff_schedule;
+        tariff_schedule.set(periods, utc_offset_secs, effective_from, effective_until)?;
+        
+        push_price_bands(
+            &ctx.accounts.trading_program,
+            &ctx.accounts.market,
+            &ctx.accounts.oracle_authority,
+            ctx.bumps.oracle_authority,
+            &ctx.accounts.protocol_params,
+            tariff_schedule,
+        )?;
+        
+        emit!(TariffScheduleUpdated {
+            authority: ctx.accounts.authority.key(),
+            period_count: tariff_schedule.periods.len() as u8,
+            effective_from,
+            effective_until,
+            timestamp: tariff_schedule.updated_at,
+        });
+        
         Ok(())
     }
 
@@ -103,29 +670,57 @@ pub mod oracle {
         Ok(())
     }
 
-    /// Update API Gateway address (admin only)
-    pub fn update_api_gateway(
-        ctx: Context<UpdateApiGateway>,
-        new_api_gateway: Pubkey,
+    /// Post a correction to a meter's totals after a reading dispute is upheld (admin only,
+    /// i.e. governance)
+    pub fn correct_meter_reading(
+        ctx: Context<CorrectMeterReading>,
+        reading_timestamp: i64,
+        generation_delta: i64,
+        consumption_delta: i64,
     ) -> Result<()> {
-        let oracle_data = &mut ctx.accounts.oracle_data;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"oracle_authority", &[ctx.bumps.oracle_authority]]];
+        registry::cpi::correct_meter_reading(
+            CpiContext::new_with_signer(
+                ctx.accounts.registry_program.to_account_info(),
+                registry::cpi::accounts::UpdateMeterReading {
+                    meter_account: ctx.accounts.meter_account.to_account_info(),
+                    pause_config: ctx.accounts.pause_config.to_account_info(),
+                    oracle_authority: ctx.accounts.oracle_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            generation_delta,
+            consumption_delta,
+            reading_timestamp,
+        )?;
         
-        require!(
-            ctx.accounts.authority.key() == oracle_data.authority,
-            ErrorCode::UnauthorizedAuthority
-        );
+        emit!(MeterReadingCorrected {
+            meter_id: ctx.accounts.meter_account.meter_id.clone(),
+            reading_timestamp,
+            generation_delta,
+            consumption_delta,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
-        let old_gateway = oracle_data.api_gateway;
-        oracle_data.api_gateway = new_api_gateway;
+        Ok(())
+    }
+    
+    /// Hand oracle administration to a new authority, e.g. the governance PDA (admin only)
+    pub fn transfer_oracle_authority(
+        ctx: Context<TransferOracleAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        let old_authority = oracle_data.authority;
+        oracle_data.authority = new_authority;
         
-        emit!(ApiGatewayUpdated {
-            authority: ctx.accounts.authority.key(),
-            old_gateway,
-            new_gateway: new_api_gateway,
+        emit!(OracleAuthorityTransferred {
+            old_authority,
+            new_authority,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
-        msg!("API Gateway updated from {} to {}", old_gateway, new_api_gateway);
         Ok(())
     }
 }
@@ -149,32 +744,382 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(meter_id: String, energy_produced: u64, energy_consumed: u64, reading_timestamp: i64)]
 pub struct SubmitMeterReading<'info> {
     #[account(mut)]
     pub oracle_data: Account<'info, OracleData>,
     
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(
+        mut,
+        seeds = [b"meter", meter_id.as_bytes()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    /// CHECK: Weather for the interval the reading falls in; solar readings are checked
+    /// against it once an observation has been recorded there
+    #[account(
+        seeds = [b"weather".as_ref(), &weather_interval_start(reading_timestamp).to_le_bytes()],
+        bump
+    )]
+    pub weather_observation: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA signer the registry accepts reading updates from
+    #[account(seeds = [b"oracle_authority"], bump)]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::OracleIngest) @ ErrorCode::OracleIngestPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
     pub authority: Signer<'info>,
+    
+    pub registry_program: Program<'info, RegistryProgram>,
 }
 
 #[derive(Accounts)]
-pub struct TriggerMarketClearing<'info> {
-    #[account(mut)]
+#[instruction(interval_start: i64)]
+pub struct SubmitWeatherObservation<'info> {
     pub oracle_data: Account<'info, OracleData>,
     
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WeatherObservation::INIT_SPACE,
+        seeds = [b"weather".as_ref(), &interval_start.to_le_bytes()],
+        bump
+    )]
+    pub weather_observation: Account<'info, WeatherObservation>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::OracleIngest) @ ErrorCode::OracleIngestPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(mut)]
     pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateOracleStatus<'info> {
-    #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority)]
+#[instruction(meter_id: String, energy_produced: u64, energy_consumed: u64, reading_timestamp: i64)]
+pub struct QuarantineMeterReading<'info> {
+    #[account(mut)]
     pub oracle_data: Account<'info, OracleData>,
     
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(
+        seeds = [b"meter", meter_id.as_bytes()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    /// CHECK: Weather for the interval the reading falls in; solar readings are checked
+    /// against it once an observation has been recorded there
+    #[account(
+        seeds = [b"weather".as_ref(), &weather_interval_start(reading_timestamp).to_le_bytes()],
+        bump
+    )]
+    pub weather_observation: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + QuarantinedReading::INIT_SPACE,
+        seeds = [b"quarantine", meter_id.as_bytes(), &reading_timestamp.to_le_bytes()],
+        bump
+    )]
+    pub quarantined_reading: Account<'info, QuarantinedReading>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::OracleIngest) @ ErrorCode::OracleIngestPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(mut)]
     pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateApiGateway<'info> {
-    #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority)]
+pub struct ReviewQuarantinedReading<'info> {
+    #[account(mut)]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(mut, has_one = submitter, close = submitter)]
+    pub quarantined_reading: Account<'info, QuarantinedReading>,
+    
+    #[account(
+        mut,
+        seeds = [b"meter", quarantined_reading.meter_id.as_bytes()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    /// CHECK: PDA signer the registry accepts reading updates from
+    #[account(seeds = [b"oracle_authority"], bump)]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    /// Mutated by governance when the review is counted as an attestation
+    #[account(mut, seeds = [b"poa_config"], bump, seeds::program = governance::ID)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"rec_validator", validator.key().as_ref()],
+        bump,
+        seeds::program = governance::ID,
+        constraint = rec_validator.is_active() @ ErrorCode::UnauthorizedValidator
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::OracleIngest) @ ErrorCode::OracleIngestPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// CHECK: Rent refund destination, must match the original submitter
+    #[account(mut)]
+    pub submitter: UncheckedAccount<'info>,
+    
+    pub validator: Signer<'info>,
+    
+    pub registry_program: Program<'info, RegistryProgram>,
+    
+    pub governance_program: Program<'info, GovernanceProgram>,
+}
+
+#[derive(Accounts)]
+pub struct CommitReadingBatch<'info> {
+    #[account(mut)]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReadingBatch::INIT_SPACE,
+        seeds = [b"reading_batch".as_ref(), &oracle_data.total_batches.to_le_bytes()],
+        bump
+    )]
+    pub reading_batch: Account<'info, ReadingBatch>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::OracleIngest) @ ErrorCode::OracleIngestPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyReadingInclusion<'info> {
+    pub reading_batch: Account<'info, ReadingBatch>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct TriggerMarketClearing<'info> {
+    #[account(mut)]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MarketEpoch::INIT_SPACE,
+        seeds = [b"market_epoch".as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub market_epoch: Account<'info, MarketEpoch>,
+    
+    #[account(mut, seeds = [b"market"], bump, seeds::program = trading::ID)]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Initialized by the trading program during the auction CPI
+    #[account(
+        mut,
+        seeds = [b"epoch_auction".as_ref(), &epoch.to_le_bytes()],
+        bump,
+        seeds::program = trading::ID
+    )]
+    pub epoch_auction: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA signer the trading market accepts auction runs from
+    #[account(seeds = [b"oracle_authority"], bump)]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Trading) @ ErrorCode::TradingPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub trading_program: Program<'info, TradingProgram>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct SettleMarketClearing<'info> {
+    #[account(mut)]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(mut, seeds = [b"market_epoch".as_ref(), &epoch.to_le_bytes()], bump)]
+    pub market_epoch: Account<'info, MarketEpoch>,
+    
+    #[account(
+        seeds = [b"epoch_auction".as_ref(), &epoch.to_le_bytes()],
+        bump,
+        seeds::program = trading::ID
+    )]
+    pub epoch_auction: Account<'info, EpochAuction>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckLiveness<'info> {
+    #[account(mut, seeds = [b"oracle_data"], bump)]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTariffSchedule<'info> {
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TariffSchedule::INIT_SPACE,
+        seeds = [b"tariff_schedule"],
+        bump
+    )]
+    pub tariff_schedule: Account<'info, TariffSchedule>,
+    
+    #[account(mut, seeds = [b"market"], bump, seeds::program = trading::ID)]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: PDA signer the trading market accepts price bands from
+    #[account(seeds = [b"oracle_authority"], bump)]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub trading_program: Program<'info, TradingProgram>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTariffSchedule<'info> {
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    #[account(mut, seeds = [b"tariff_schedule"], bump)]
+    pub tariff_schedule: Account<'info, TariffSchedule>,
+    
+    #[account(mut, seeds = [b"market"], bump, seeds::program = trading::ID)]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: PDA signer the trading market accepts price bands from
+    #[account(seeds = [b"oracle_authority"], bump)]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub trading_program: Program<'info, TradingProgram>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOracleStatus<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority)]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// The meter follows the authority so governance can pass it as its dispute target
+#[derive(Accounts)]
+pub struct CorrectMeterReading<'info> {
+    #[account(has_one = authority @ ErrorCode::UnauthorizedAuthority)]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    #[account(seeds = [b"pause_config"], bump, seeds::program = governance::ID)]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// CHECK: PDA signer the registry accepts reading updates from
+    #[account(seeds = [b"oracle_authority"], bump)]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    pub registry_program: Program<'info, RegistryProgram>,
+}
+
+#[derive(Accounts)]
+pub struct TransferOracleAuthority<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority)]
     pub oracle_data: Account<'info, OracleData>,
     
     pub authority: Signer<'info>,
@@ -185,12 +1130,439 @@ pub struct UpdateApiGateway<'info> {
 #[derive(InitSpace)]
 pub struct OracleData {
     pub authority: Pubkey,
-    pub api_gateway: Pubkey,        // Only API Gateway can call oracle functions
     pub total_readings: u64,
     pub last_reading_timestamp: i64,
     pub last_clearing: i64,
     pub active: bool,
     pub created_at: i64,
+    pub total_batches: u64,
+    pub clearing_epoch: u64,          // Epoch most recently moved to Clearing
+    pub clearing_in_progress: bool,
+    pub frozen_until: i64,            // Readings before this timestamp belong to a clearing or settled epoch
+    pub degraded: bool,               // Set when readings go stale; blocks clearing
+    pub degraded_since: i64,
+    pub flagged_readings: u64,        // Solar readings that deviated from the weather-based expectation
+    pub last_ingest_at: i64,          // Cluster time readings last arrived; reported timestamps can't fake freshness
+}
+
+impl OracleData {
+    /// Whether nothing has been ingested for more than `max_staleness_secs`
+    pub fn is_stale(&self, now: i64, max_staleness_secs: i64) -> bool {
+        now - self.last_ingest_at > max_staleness_secs
+    }
+}
+
+/// Start (inclusive) and end (exclusive) timestamps of `epoch` for epochs of `epoch_duration_secs`
+pub fn epoch_bounds(epoch: u64, epoch_duration_secs: i64) -> Result<(i64, i64)> {
+    let start = i64::try_from(epoch)
+        .ok()
+        .and_then(|e| e.checked_mul(epoch_duration_secs))
+        .ok_or(ErrorCode::InvalidEpoch)?;
+    let end = start
+        .checked_add(epoch_duration_secs)
+        .ok_or(ErrorCode::InvalidEpoch)?;
+    Ok((start, end))
+}
+
+/// Clearing lifecycle of an epoch; epochs after `OracleData::frozen_until` are still open
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EpochStatus {
+    Clearing,
+    Settled,
+}
+
+/// Clearing record for one trading epoch
+#[account]
+#[derive(InitSpace)]
+pub struct MarketEpoch {
+    pub epoch: u64,
+    pub epoch_start: i64,
+    pub epoch_end: i64,
+    pub status: EpochStatus,
+    pub clearing_price: u64,
+    pub cleared_volume: u64,
+    pub clearing_started_at: i64,
+    pub settled_at: i64,
+}
+
+/// Merkle commitment over the readings of one interval
+#[account]
+#[derive(InitSpace)]
+pub struct ReadingBatch {
+    pub batch_id: u64,
+    pub merkle_root: [u8; 32],
+    pub interval_start: i64,
+    pub interval_secs: u32,
+    pub reading_count: u32,
+    pub submitter: Pubkey,
+    pub committed_at: i64,
+}
+
+impl ReadingBatch {
+    /// Whether `timestamp` falls inside this batch's interval
+    pub fn covers(&self, timestamp: i64) -> Result<bool> {
+        let interval_end = self
+            .interval_start
+            .checked_add(self.interval_secs as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(timestamp >= self.interval_start && timestamp < interval_end)
+    }
+}
+
+/// Campus weather for one interval
+#[account]
+#[derive(InitSpace)]
+pub struct WeatherObservation {
+    pub interval_start: i64,
+    pub irradiance_w_m2: u32,       // Global horizontal irradiance
+    pub temperature_centi_c: i32,   // Ambient temperature in hundredths of a °C
+    pub submitter: Pubkey,
+    pub recorded_at: i64,
+}
+
+/// Start of the weather interval a reading falls in; readings are stamped at the end of their period
+pub fn weather_interval_start(reading_timestamp: i64) -> i64 {
+    (reading_timestamp - 1).div_euclid(WEATHER_INTERVAL_SECS) * WEATHER_INTERVAL_SECS
+}
+
+/// Energy a solar installation should produce over `interval_secs` under the observed weather.
+///
+/// Scales nameplate output by irradiance relative to the 1000 W/m² test condition, the
+/// performance ratio, and a linear derate for ambient temperature above 25 °C.
+pub fn expected_solar_yield_wh(
+    rated_capacity_w: u64,
+    interval_secs: i64,
+    weather: &WeatherObservation,
+) -> u64 {
+    let derate_bps = ((weather.temperature_centi_c as i64 - 2_500).max(0) * SOLAR_TEMP_DERATE_BPS_PER_C / 100)
+        .min(10_000) as u128;
+    
+    let expected = (rated_capacity_w as u128)
+        .saturating_mul(interval_secs.max(0) as u128)
+        .saturating_mul(weather.irradiance_w_m2 as u128)
+        .saturating_mul(SOLAR_PERFORMANCE_RATIO_BPS)
+        .saturating_mul(10_000 - derate_bps)
+        / (3_600 * 1_000 * 10_000 * 10_000);
+    
+    expected.min(u64::MAX as u128) as u64
+}
+
+/// Whether produced energy is too far from the expected solar yield, in either direction
+pub fn solar_yield_deviates(
+    rated_capacity_w: u64,
+    interval_secs: i64,
+    energy_produced: u64,
+    expected_wh: u64,
+) -> bool {
+    let rated_wh = (rated_capacity_w as u128).saturating_mul(interval_secs.max(0) as u128) / 3_600;
+    let allowed = (expected_wh as u128) * SOLAR_DEVIATION_TOLERANCE_BPS / 10_000
+        + rated_wh * SOLAR_DEVIATION_FLOOR_BPS / 10_000;
+    
+    (energy_produced as u128).abs_diff(expected_wh as u128) > allowed
+}
+
+/// Expected yield and the observation, if a solar meter's reading deviates from the
+/// weather observed over its interval; `None` if it doesn't or the meter isn't solar
+///
+/// `weather_info` must be the interval's `[b"weather", interval]` PDA, so a submitter
+/// can't opt out by leaving it out; the check only lapses while nothing is recorded there.
+pub fn solar_deviation(
+    meter: &MeterAccount,
+    energy_produced: u64,
+    interval_secs: i64,
+    weather_info: &AccountInfo,
+) -> Result<Option<(u64, WeatherObservation)>> {
+    if meter.meter_type != MeterType::Solar || weather_info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*weather_info.owner, crate::ID, ErrorCode::InvalidWeatherObservation);
+    let weather = WeatherObservation::try_deserialize(&mut &weather_info.try_borrow_data()?[..])?;
+    
+    let expected_wh = expected_solar_yield_wh(meter.rated_capacity_w, interval_secs, &weather);
+    let deviates = solar_yield_deviates(meter.rated_capacity_w, interval_secs, energy_produced, expected_wh);
+    Ok(deviates.then_some((expected_wh, weather)))
+}
+
+/// A reading held back for REC validator review
+#[account]
+#[derive(InitSpace)]
+pub struct QuarantinedReading {
+    #[max_len(50)]
+    pub meter_id: String,
+    pub energy_produced: u64,
+    pub energy_consumed: u64,
+    pub reading_timestamp: i64,
+    pub submitter: Pubkey,
+    pub quarantined_at: i64,
+}
+
+/// Utility prices for one time-of-use period, in token base units per kWh
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TariffPeriod {
+    pub start_hour: u8,       // Local hour, inclusive
+    pub end_hour: u8,         // Local hour, exclusive
+    pub import_price: u64,    // Retail price for energy bought from the grid
+    pub feed_in_price: u64,   // Credit for energy exported to the grid
+}
+
+/// Grid reference tariff; periods tile the local day from 00:00 to 24:00
+#[account]
+#[derive(InitSpace)]
+pub struct TariffSchedule {
+    #[max_len(MAX_TARIFF_PERIODS)]
+    pub periods: Vec<TariffPeriod>,
+    pub utc_offset_secs: i32,      // Campus local time used for period hours
+    pub effective_from: i64,
+    pub effective_until: i64,      // 0 = open-ended
+    pub updated_at: i64,
+}
+
+impl TariffSchedule {
+    /// Validate and store a new schedule
+    pub fn set(
+        &mut self,
+        periods: Vec<TariffPeriod>,
+        utc_offset_secs: i32,
+        effective_from: i64,
+        effective_until: i64,
+    ) -> Result<()> {
+        require!(
+            !periods.is_empty() && periods.len() <= MAX_TARIFF_PERIODS,
+            ErrorCode::InvalidTariffSchedule
+        );
+        require!(
+            periods[0].start_hour == 0 && periods[periods.len() - 1].end_hour == 24,
+            ErrorCode::InvalidTariffSchedule
+        );
+        for (i, period) in periods.iter().enumerate() {
+            require!(
+                period.start_hour < period.end_hour
+                    && period.feed_in_price <= period.import_price
+                    && (i == 0 || periods[i - 1].end_hour == period.start_hour),
+                ErrorCode::InvalidTariffSchedule
+            );
+        }
+        require!(
+            (-14 * 3_600..=14 * 3_600).contains(&utc_offset_secs),
+            ErrorCode::InvalidTariffSchedule
+        );
+        require!(
+            effective_until == 0 || effective_until > effective_from,
+            ErrorCode::InvalidTariffSchedule
+        );
+        
+        self.periods = periods;
+        self.utc_offset_secs = utc_offset_secs;
+        self.effective_from = effective_from;
+        self.effective_until = effective_until;
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+    
+    /// Period in force at `timestamp`, or `None` outside the effective dates
+    pub fn period_at(&self, timestamp: i64) -> Option<&TariffPeriod> {
+        if timestamp < self.effective_from
+            || (self.effective_until != 0 && timestamp >= self.effective_until)
+        {
+            return None;
+        }
+        
+        let local_hour = (timestamp + self.utc_offset_secs as i64).rem_euclid(86_400) / 3_600;
+        self.periods
+            .iter()
+            .find(|p| local_hour >= p.start_hour as i64 && local_hour < p.end_hour as i64)
+    }
+    
+    /// Lowest feed-in price and highest import price across all periods
+    pub fn price_bands(&self) -> (u64, u64) {
+        let min_price = self.periods.iter().map(|p| p.feed_in_price).min().unwrap_or(0);
+        let max_price = self.periods.iter().map(|p| p.import_price).max().unwrap_or(0);
+        (min_price, max_price)
+    }
+}
+
+/// Result of checking a reading against a meter's physical limits
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ReadingAssessment {
+    Plausible,
+    ExceedsCapacity,
+}
+
+/// Seconds covered by a reading, measured from the meter's previous reading and capped
+/// at `max_interval_secs`, so a long gap cannot excuse an arbitrarily large reading
+pub fn reading_interval(meter: &MeterAccount, reading_timestamp: i64, max_interval_secs: i64) -> Result<i64> {
+    if meter.last_reading_at > 0 {
+        require!(
+            reading_timestamp > meter.last_reading_at,
+            ErrorCode::InvalidMeterReading
+        );
+        Ok((reading_timestamp - meter.last_reading_at).min(max_interval_secs))
+    } else {
+        Ok(DEFAULT_READING_INTERVAL_SECS)
+    }
+}
+
+/// Reject a reported timestamp more than `MAX_CLOCK_DRIFT_SECS` ahead of the cluster clock
+fn require_not_future(timestamp: i64, now: i64) -> Result<()> {
+    require!(
+        timestamp <= now.saturating_add(MAX_CLOCK_DRIFT_SECS),
+        ErrorCode::TimestampInFuture
+    );
+    Ok(())
+}
+
+/// Check a reading against the meter's rated capacity and installation profile.
+///
+/// Physically impossible readings (e.g. generation on a grid meter) are an error;
+/// readings above rated capacity × interval are reported as `ExceedsCapacity`.
+pub fn assess_reading(
+    meter: &MeterAccount,
+    energy_produced: u64,
+    energy_consumed: u64,
+    interval_secs: i64,
+) -> Result<ReadingAssessment> {
+    let generation_impossible = meter.meter_type == MeterType::Grid
+        || meter.installation_profile == InstallationProfile::ConsumptionOnly;
+    let consumption_impossible = meter.installation_profile == InstallationProfile::GenerationOnly;
+    
+    require!(
+        !(generation_impossible && energy_produced > 0),
+        ErrorCode::ImpossibleReading
+    );
+    require!(
+        !(consumption_impossible && energy_consumed > 0),
+        ErrorCode::ImpossibleReading
+    );
+    
+    // Wh = W × s / 3600, plus tolerance for meter and clock drift
+    let max_energy_wh = (meter.rated_capacity_w as u128)
+        .saturating_mul(interval_secs as u128)
+        .saturating_mul(10_000 + CAPACITY_TOLERANCE_BPS)
+        / (3_600 * 10_000);
+    
+    if energy_produced as u128 > max_energy_wh || energy_consumed as u128 > max_energy_wh {
+        return Ok(ReadingAssessment::ExceedsCapacity);
+    }
+    
+    Ok(ReadingAssessment::Plausible)
+}
+
+/// Post an accepted reading to the registry, signed by the oracle authority PDA
+#[allow(clippy::too_many_arguments)]
+fn post_reading_to_registry<'info>(
+    registry_program: &Program<'info, RegistryProgram>,
+    meter_account: &Account<'info, MeterAccount>,
+    pause_config: &Account<'info, PauseConfig>,
+    oracle_authority: &UncheckedAccount<'info>,
+    oracle_authority_bump: u8,
+    energy_produced: u64,
+    energy_consumed: u64,
+    reading_timestamp: i64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"oracle_authority", &[oracle_authority_bump]]];
+    
+    let cpi_accounts = registry::cpi::accounts::UpdateMeterReading {
+        meter_account: meter_account.to_account_info(),
+        pause_config: pause_config.to_account_info(),
+        oracle_authority: oracle_authority.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        registry_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    
+    registry::cpi::update_meter_reading(cpi_ctx, energy_produced, energy_consumed, reading_timestamp)
+}
+
+/// Flip between live and degraded based on data freshness, emitting an event on each transition
+fn update_liveness(oracle_data: &mut OracleData, params: &ParamValues, now: i64) {
+    let stale = oracle_data.is_stale(now, params.max_staleness_secs);
+    
+    if stale && !oracle_data.degraded {
+        oracle_data.degraded = true;
+        oracle_data.degraded_since = now;
+        
+        emit!(OracleDegraded {
+            last_ingest_at: oracle_data.last_ingest_at,
+            max_staleness_secs: params.max_staleness_secs,
+            timestamp: now,
+        });
+    } else if !stale && oracle_data.degraded {
+        oracle_data.degraded = false;
+        
+        emit!(OracleRecovered {
+            last_ingest_at: oracle_data.last_ingest_at,
+            degraded_secs: now - oracle_data.degraded_since,
+            timestamp: now,
+        });
+    }
+}
+
+/// Publish the schedule's price bands to the trading market, signed by the oracle authority PDA,
+/// once they are within the protocol's price limits
+fn push_price_bands<'info>(
+    trading_program: &Program<'info, TradingProgram>,
+    market: &Account<'info, Market>,
+    oracle_authority: &UncheckedAccount<'info>,
+    oracle_authority_bump: u8,
+    protocol_params: &ProtocolParams,
+    tariff_schedule: &TariffSchedule,
+) -> Result<()> {
+    let (min_price, max_price) = tariff_schedule.price_bands();
+    require!(
+        protocol_params.params.allows_price_band(min_price, max_price),
+        ErrorCode::PriceBandOutOfRange
+    );
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[b"oracle_authority", &[oracle_authority_bump]]];
+    
+    let cpi_accounts = trading::cpi::accounts::UpdatePriceBands {
+        market: market.to_account_info(),
+        clearing_authority: oracle_authority.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        trading_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    
+    trading::cpi::update_price_bands(
+        cpi_ctx,
+        min_price,
+        max_price,
+        tariff_schedule.effective_from,
+        tariff_schedule.effective_until,
+    )
+}
+
+/// Merkle leaf for a reading: sha256(0x00 || len(meter_id) || meter_id || produced || consumed || timestamp)
+pub fn reading_leaf(
+    meter_id: &str,
+    energy_produced: u64,
+    energy_consumed: u64,
+    reading_timestamp: i64,
+) -> [u8; 32] {
+    hashv(&[
+        &[0u8],
+        &(meter_id.len() as u32).to_le_bytes(),
+        meter_id.as_bytes(),
+        &energy_produced.to_le_bytes(),
+        &energy_consumed.to_le_bytes(),
+        &reading_timestamp.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Verify a sorted-pair Merkle proof; interior nodes are sha256(0x01 || min || max)
+pub fn verify_reading_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[&[1u8], &left, &right]).to_bytes()
+    });
+    computed == root
 }
 
 // Events
@@ -203,9 +1575,97 @@ pub struct MeterReadingSubmitted {
     pub submitter: Pubkey,
 }
 
+#[event]
+pub struct WeatherObservationRecorded {
+    pub interval_start: i64,
+    pub irradiance_w_m2: u32,
+    pub temperature_centi_c: i32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolarReadingFlagged {
+    pub meter_id: String,
+    pub energy_produced: u64,
+    pub expected_wh: u64,
+    pub irradiance_w_m2: u32,
+    pub temperature_centi_c: i32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReadingQuarantined {
+    pub meter_id: String,
+    pub energy_produced: u64,
+    pub energy_consumed: u64,
+    pub rated_capacity_w: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuarantinedReadingReviewed {
+    pub meter_id: String,
+    pub validator: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReadingBatchCommitted {
+    pub batch: Pubkey,
+    pub batch_id: u64,
+    pub merkle_root: [u8; 32],
+    pub interval_start: i64,
+    pub interval_secs: u32,
+    pub reading_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReadingInclusionVerified {
+    pub batch: Pubkey,
+    pub meter_id: String,
+    pub energy_produced: u64,
+    pub energy_consumed: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MarketClearingTriggered {
     pub authority: Pubkey,
+    pub epoch: u64,
+    pub frozen_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketEpochSettled {
+    pub epoch: u64,
+    pub clearing_price: u64,
+    pub cleared_volume: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleDegraded {
+    pub last_ingest_at: i64,
+    pub max_staleness_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleRecovered {
+    pub last_ingest_at: i64,
+    pub degraded_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TariffScheduleUpdated {
+    pub authority: Pubkey,
+    pub period_count: u8,
+    pub effective_from: i64,
+    pub effective_until: i64,
     pub timestamp: i64,
 }
 
@@ -217,10 +1677,19 @@ pub struct OracleStatusUpdated {
 }
 
 #[event]
-pub struct ApiGatewayUpdated {
+pub struct MeterReadingCorrected {
+    pub meter_id: String,
+    pub reading_timestamp: i64,
+    pub generation_delta: i64,
+    pub consumption_delta: i64,
     pub authority: Pubkey,
-    pub old_gateway: Pubkey,
-    pub new_gateway: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleAuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
     pub timestamp: i64,
 }
 
@@ -237,4 +1706,46 @@ pub enum ErrorCode {
     InvalidMeterReading,
     #[msg("Market clearing in progress")]
     MarketClearingInProgress,
+    #[msg("Reading is physically impossible for this meter")]
+    ImpossibleReading,
+    #[msg("Reading exceeds rated capacity and must be quarantined")]
+    ReadingExceedsCapacity,
+    #[msg("Reading is within capacity and cannot be quarantined")]
+    ReadingNotQuarantinable,
+    #[msg("Signer is not an active REC validator")]
+    UnauthorizedValidator,
+    #[msg("Reading batch must cover a non-empty interval")]
+    InvalidReadingBatch,
+    #[msg("Reading timestamp is outside the batch interval")]
+    ReadingOutsideBatch,
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Reading belongs to an epoch that is clearing or settled")]
+    EpochFrozen,
+    #[msg("Epoch has not ended yet")]
+    EpochNotEnded,
+    #[msg("Minimum interval between clearings has not elapsed")]
+    ClearingTooFrequent,
+    #[msg("Epoch is not the one currently clearing")]
+    EpochNotClearing,
+    #[msg("Invalid epoch")]
+    InvalidEpoch,
+    #[msg("Tariff periods must tile the day with feed-in at or below import price")]
+    InvalidTariffSchedule,
+    #[msg("Oracle data is stale; market clearing is halted")]
+    OracleDegraded,
+    #[msg("Weather observation is out of range or not aligned to an interval")]
+    InvalidWeatherObservation,
+    #[msg("Oracle ingestion is paused")]
+    OracleIngestPaused,
+    #[msg("Trading is paused")]
+    TradingPaused,
+    #[msg("Tariff price band is outside the protocol price limits")]
+    PriceBandOutOfRange,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Timestamp is ahead of the cluster clock")]
+    TimestampInFuture,
+    #[msg("Solar reading deviates from the recorded weather and must be quarantined")]
+    SolarYieldDeviates,
 }
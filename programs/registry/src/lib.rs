@@ -1,4 +1,27 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use anchor_lang::system_program;
+use governance::program::Governance as GovernanceProgram;
+use governance::{PauseConfig, PauseScope, PoAConfig, RecValidator};
+
+/// Maximum number of keys holding registry roles
+pub const MAX_ROLE_MEMBERS: usize = 32;
+
+/// Maximum meters provisioned by one `assign_meters_batch` call
+pub const MAX_BATCH_ASSIGNMENTS: usize = 10;
+
+/// Layout version written to `Registry`, `UserAccount` and `MeterAccount`.
+/// Accounts created before versioning read as version 0.
+///
+/// 1: version field; users placed on a grid node instead of a free-text location;
+///    meter capacity, installation profile, grid node and activation record
+/// 2: campus identity attestation on `UserAccount`
+#[constant]
+pub const ACCOUNT_VERSION: u8 = 2;
+
+/// The oracle program, whose `oracle_authority` PDA alone posts meter readings. The
+/// oracle depends on this crate, so its id is pinned here rather than imported.
+pub const ORACLE_PROGRAM_ID: Pubkey = pubkey!("7sA8No5jojLboTzQQTU3fiAL8kGAjTzPgXtaMEYNKPEC");
 
 declare_id!("BkZ9cBB3FFGXxJPw5JTJAbVAQYaDBeoQKoSL7b5u5Snv");
 
@@ -13,6 +36,12 @@ pub mod registry {
         registry.user_count = 0;
         registry.meter_count = 0;
         registry.created_at = Clock::get()?.unix_timestamp;
+        registry.version = ACCOUNT_VERSION;
+        
+        // The initializing key bootstraps the role table as its first admin
+        let role_table = &mut ctx.accounts.role_table;
+        role_table.members = Vec::new();
+        role_table.grant(ctx.accounts.authority.key(), Role::RegistryAdmin)?;
         
         emit!(RegistryInitialized {
             authority: ctx.accounts.authority.key(),
@@ -22,23 +51,107 @@ pub mod registry {
         Ok(())
     }
     
+    /// Add a node to the campus grid topology (admin only)
+    ///
+    /// Nodes nest Campus → Building → Feeder → Transformer; a parent, when given,
+    /// must sit exactly one level above the new node.
+    pub fn create_grid_node(
+        ctx: Context<CreateGridNode>,
+        node_id: String,
+        node_type: GridNodeType,
+        capacity_w: u64,
+    ) -> Result<()> {
+        require!(
+            !node_id.is_empty() && node_id.len() <= 32,
+            ErrorCode::InvalidGridNode
+        );
+        require!(capacity_w > 0, ErrorCode::InvalidRatedCapacity);
+        
+        let parent = match &ctx.accounts.parent {
+            Some(parent) => {
+                require!(
+                    node_type.parent_type() == Some(parent.node_type),
+                    ErrorCode::InvalidGridTopology
+                );
+                Some(parent.key())
+            }
+            None => None,
+        };
+        
+        let grid_node = &mut ctx.accounts.grid_node;
+        grid_node.node_id = node_id.clone();
+        grid_node.node_type = node_type;
+        grid_node.parent = parent;
+        grid_node.capacity_w = capacity_w;
+        grid_node.connected_capacity_w = 0;
+        grid_node.user_count = 0;
+        grid_node.meter_count = 0;
+        grid_node.created_at = Clock::get()?.unix_timestamp;
+        
+        emit!(GridNodeCreated {
+            node: grid_node.key(),
+            node_id,
+            node_type,
+            parent,
+            capacity_w,
+            timestamp: grid_node.created_at,
+        });
+        
+        Ok(())
+    }
+    
+    /// Change a grid node's capacity limit (admin only)
+    pub fn update_grid_node_capacity(
+        ctx: Context<UpdateGridNodeCapacity>,
+        capacity_w: u64,
+    ) -> Result<()> {
+        let grid_node = &mut ctx.accounts.grid_node;
+        
+        // Cannot shrink below what is already connected
+        require!(
+            capacity_w > 0 && capacity_w >= grid_node.connected_capacity_w,
+            ErrorCode::GridNodeCapacityExceeded
+        );
+        
+        let old_capacity_w = grid_node.capacity_w;
+        grid_node.capacity_w = capacity_w;
+        
+        emit!(GridNodeCapacityUpdated {
+            node: grid_node.key(),
+            old_capacity_w,
+            new_capacity_w: capacity_w,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
     /// Register a new user in the P2P energy trading system
     pub fn register_user(
         ctx: Context<RegisterUser>,
         user_type: UserType,
-        location: String,
     ) -> Result<()> {
         let user_account = &mut ctx.accounts.user_account;
         let registry = &mut ctx.accounts.registry;
         
+        // Users not yet mapped onto the topology keep the default node
+        let grid_node = match ctx.accounts.grid_node.as_mut() {
+            Some(node) => {
+                node.user_count += 1;
+                node.key()
+            }
+            None => Pubkey::default(),
+        };
+        
         // Set user account data
         user_account.authority = ctx.accounts.user_authority.key();
         user_account.user_type = user_type;
-        user_account.location = location.clone();
+        user_account.grid_node = grid_node;
         user_account.status = UserStatus::Active;
         user_account.registered_at = Clock::get()?.unix_timestamp;
         user_account.meter_count = 0;
         user_account.created_at = Clock::get()?.unix_timestamp; // For backward compatibility
+        user_account.version = ACCOUNT_VERSION;
         
         // Update registry counters
         registry.user_count += 1;
@@ -46,7 +159,7 @@ pub mod registry {
         emit!(UserRegistered {
             user: ctx.accounts.user_authority.key(),
             user_type,
-            location,
+            grid_node,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
@@ -54,10 +167,15 @@ pub mod registry {
     }
     
     /// Register a smart meter for an existing user
+    ///
+    /// The meter starts out pending and records no readings until a REC validator
+    /// has inspected the installation and activated it.
     pub fn register_meter(
         ctx: Context<RegisterMeter>,
         meter_id: String,
         meter_type: MeterType,
+        rated_capacity_w: u64,
+        installation_profile: InstallationProfile,
     ) -> Result<()> {
         let meter_account = &mut ctx.accounts.meter_account;
         let user_account = &mut ctx.accounts.user_account;
@@ -69,43 +187,63 @@ pub mod registry {
             ErrorCode::UnauthorizedUser
         );
         
-        // Set meter account data
-        meter_account.meter_id = meter_id.clone();
-        meter_account.owner = ctx.accounts.user_authority.key();
-        meter_account.meter_type = meter_type;
-        meter_account.status = MeterStatus::Active;
-        meter_account.registered_at = Clock::get()?.unix_timestamp;
-        meter_account.last_reading_at = 0;
-        meter_account.total_generation = 0;
-        meter_account.total_consumption = 0;
+        let assignment = MeterAssignment {
+            meter_id,
+            owner: ctx.accounts.user_authority.key(),
+            meter_type,
+            rated_capacity_w,
+            installation_profile,
+        };
+        assignment.validate()?;
+        
+        let grid_node = connect_to_grid_node(ctx.accounts.grid_node.as_mut(), rated_capacity_w)?;
+        meter_account.set_inner(MeterAccount::pending(&assignment, grid_node, Clock::get()?.unix_timestamp));
         
         // Update counters
         user_account.meter_count += 1;
         registry.meter_count += 1;
         
-        emit!(MeterRegistered {
-            meter_id: meter_id.clone(),
-            owner: ctx.accounts.user_authority.key(),
-            meter_type,
-            timestamp: Clock::get()?.unix_timestamp,
+        emit_meter_registered(meter_account);
+        
+        Ok(())
+    }
+    
+    /// Bind a user to a campus identity (IdentityAttestor only)
+    ///
+    /// `identity_hash` is the gateway's salted hash of the university ID, so the same
+    /// person always hashes the same way and can only ever attest one wallet.
+    pub fn attest_user(ctx: Context<AttestUser>, identity_hash: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let user_account = &mut ctx.accounts.user_account;
+        
+        require!(identity_hash != [0u8; 32], ErrorCode::InvalidIdentityHash);
+        require!(!user_account.is_attested(), ErrorCode::UserAlreadyAttested);
+        
+        user_account.identity_hash = identity_hash;
+        user_account.attested_by = ctx.accounts.attestor.key();
+        user_account.attested_at = now;
+        
+        let identity_record = &mut ctx.accounts.identity_record;
+        identity_record.identity_hash = identity_hash;
+        identity_record.user = user_account.authority;
+        identity_record.attested_at = now;
+        
+        emit!(UserAttested {
+            user: user_account.authority,
+            identity_hash,
+            attestor: ctx.accounts.attestor.key(),
+            timestamp: now,
         });
         
         Ok(())
     }
     
-    /// Update user status (admin only)
+    /// Update user status (RegistryAdmin only)
     pub fn update_user_status(
         ctx: Context<UpdateUserStatus>,
         new_status: UserStatus,
     ) -> Result<()> {
         let user_account = &mut ctx.accounts.user_account;
-        let registry = &ctx.accounts.registry;
-        
-        // Only registry authority can update user status
-        require!(
-            ctx.accounts.authority.key() == registry.authority,
-            ErrorCode::UnauthorizedAuthority
-        );
         
         let old_status = user_account.status;
         user_account.status = new_status;
@@ -120,7 +258,327 @@ pub mod registry {
         Ok(())
     }
     
-    /// Update meter reading (for oracles and authorized services)
+    /// Move a meter to another registered user.
+    ///
+    /// Needs either both the current and new owner's signatures, or a RegistryAdmin's
+    /// approval (e.g. when a building changes department).
+    pub fn transfer_meter(ctx: Context<TransferMeter>) -> Result<()> {
+        let role_table = &ctx.accounts.role_table;
+        let meter_account = &mut ctx.accounts.meter_account;
+        let from_user = &mut ctx.accounts.from_user_account;
+        let to_user = &mut ctx.accounts.to_user_account;
+        
+        let signed_by = |signer: &Option<Signer>, expected: Pubkey| {
+            signer.as_ref().is_some_and(|s| s.key() == expected)
+        };
+        let approved_by_admin = ctx
+            .accounts
+            .admin
+            .as_ref()
+            .is_some_and(|admin| role_table.has_role(&admin.key(), Role::RegistryAdmin));
+        let approved_by_owners = signed_by(&ctx.accounts.current_owner, from_user.authority)
+            && signed_by(&ctx.accounts.new_owner, to_user.authority);
+        require!(
+            approved_by_admin || approved_by_owners,
+            ErrorCode::UnauthorizedMeterTransfer
+        );
+        
+        require!(
+            from_user.authority != to_user.authority,
+            ErrorCode::InvalidMeterTransfer
+        );
+        require!(
+            to_user.status == UserStatus::Active,
+            ErrorCode::InvalidUserStatus
+        );
+        
+        meter_account.owner = to_user.authority;
+        from_user.meter_count = from_user
+            .meter_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        to_user.meter_count = to_user
+            .meter_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        emit!(MeterTransferred {
+            meter_id: meter_account.meter_id.clone(),
+            from_owner: from_user.authority,
+            to_owner: to_user.authority,
+            approved_by_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Activate a pending meter after an installation inspection (REC validators only)
+    ///
+    /// `rated_capacity_w` records the inspected nameplate capacity of meters migrated from
+    /// before it was stored, and must be `None` for all others.
+    pub fn activate_meter(
+        ctx: Context<ActivateMeter>,
+        inspection_reference: String,
+        rated_capacity_w: Option<u64>,
+    ) -> Result<()> {
+        require!(
+            !inspection_reference.is_empty() && inspection_reference.len() <= 64,
+            ErrorCode::InvalidInspectionReference
+        );
+        
+        let meter_account = &mut ctx.accounts.meter_account;
+        require!(
+            meter_account.status == MeterStatus::Pending,
+            ErrorCode::InvalidMeterStatus
+        );
+        
+        // Only meters migrated without a recorded capacity take one at inspection
+        match rated_capacity_w {
+            Some(capacity) => {
+                require!(
+                    meter_account.rated_capacity_w == 0 && capacity > 0,
+                    ErrorCode::InvalidRatedCapacity
+                );
+                meter_account.rated_capacity_w = capacity;
+            }
+            None => require!(meter_account.rated_capacity_w > 0, ErrorCode::InvalidRatedCapacity),
+        }
+        
+        let now = Clock::get()?.unix_timestamp;
+        meter_account.status = MeterStatus::Active;
+        meter_account.inspection_reference = inspection_reference.clone();
+        meter_account.activated_by = ctx.accounts.validator.key();
+        meter_account.activated_at = now;
+        
+        // The inspection is a certification and counts toward the validator's reliability
+        let signer_seeds: &[&[&[u8]]] = &[&[b"registry_authority", &[ctx.bumps.registry_authority]]];
+        governance::cpi::record_attestation(
+            CpiContext::new_with_signer(
+                ctx.accounts.governance_program.to_account_info(),
+                governance::cpi::accounts::RecordAttestation {
+                    poa_config: ctx.accounts.poa_config.to_account_info(),
+                    rec_validator: ctx.accounts.rec_validator.to_account_info(),
+                    reporter: ctx.accounts.registry_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.accounts.validator.key(),
+        )?;
+        
+        emit!(MeterActivated {
+            meter_id: meter_account.meter_id.clone(),
+            owner: meter_account.owner,
+            validator: ctx.accounts.validator.key(),
+            inspection_reference,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Put a meter into or out of maintenance, or mark it inactive (owner, RegistryAdmin or MeterInstaller)
+    pub fn set_meter_status(
+        ctx: Context<SetMeterStatus>,
+        new_status: MeterStatus,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let meter_key = ctx.accounts.meter_account.key();
+        let meter_account = &mut ctx.accounts.meter_account;
+        let authority = ctx.accounts.authority.key();
+        
+        // Facilities managers may act for the owner through a delegation
+        let delegated = ctx.accounts.delegation.as_ref().is_some_and(|delegation| {
+            delegation.owner == meter_account.owner
+                && delegation.authorizes(&authority, &meter_key, DelegationScope::MeterStatus, now)
+        });
+        
+        let role_table = &ctx.accounts.role_table;
+        require!(
+            authority == meter_account.owner
+                || delegated
+                || role_table.has_role(&authority, Role::RegistryAdmin)
+                || role_table.has_role(&authority, Role::MeterInstaller),
+            ErrorCode::UnauthorizedUser
+        );
+        
+        // Only a validator activation can move a meter out of (or into) pending
+        require!(
+            meter_account.status != MeterStatus::Pending && new_status != MeterStatus::Pending,
+            ErrorCode::InvalidMeterStatus
+        );
+        
+        let old_status = meter_account.status;
+        meter_account.status = new_status;
+        
+        emit!(MeterStatusUpdated {
+            meter_id: meter_account.meter_id.clone(),
+            authority,
+            old_status,
+            new_status,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Authorize another key to operate one of the caller's meters, or all of them when
+    /// `meter` is the default key, within `scopes` until `expires_at` (0 = until revoked)
+    pub fn create_delegation(
+        ctx: Context<CreateDelegation>,
+        delegate: Pubkey,
+        meter: Pubkey,
+        scopes: Vec<DelegationScope>,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let owner = ctx.accounts.owner.key();
+        
+        require!(
+            ctx.accounts.user_account.status == UserStatus::Active,
+            ErrorCode::InvalidUserStatus
+        );
+        require!(
+            delegate != owner && !scopes.is_empty() && (expires_at == 0 || expires_at > now),
+            ErrorCode::InvalidDelegation
+        );
+        
+        // A single-meter delegation must name a meter the caller owns
+        if meter != Pubkey::default() {
+            let meter_account = ctx
+                .accounts
+                .meter_account
+                .as_ref()
+                .ok_or(ErrorCode::InvalidDelegation)?;
+            require_keys_eq!(meter_account.key(), meter, ErrorCode::InvalidDelegation);
+            require_keys_eq!(meter_account.owner, owner, ErrorCode::UnauthorizedUser);
+        }
+        
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.owner = owner;
+        delegation.delegate = delegate;
+        delegation.meter = meter;
+        delegation.scopes = scopes.iter().fold(0, |bits, scope| bits | scope.bit());
+        delegation.expires_at = expires_at;
+        delegation.created_at = now;
+        
+        emit!(DelegationCreated {
+            owner,
+            delegate,
+            meter,
+            scopes: delegation.scopes,
+            expires_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Withdraw a delegation and reclaim its rent (owner only)
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        let delegation = &ctx.accounts.delegation;
+        
+        emit!(DelegationRevoked {
+            owner: delegation.owner,
+            delegate: delegation.delegate,
+            meter: delegation.meter,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Decommission a meter and close its account, returning rent to the owner (owner or RegistryAdmin)
+    ///
+    /// The meter must be out of service (inactive or in maintenance) or still awaiting
+    /// inspection. A `MeterTombstone` is left under its ID, so it can't be registered
+    /// again with a clean history.
+    pub fn decommission_meter(ctx: Context<DecommissionMeter>) -> Result<()> {
+        let meter_account = &ctx.accounts.meter_account;
+        let authority = ctx.accounts.authority.key();
+        
+        require!(
+            authority == meter_account.owner
+                || ctx.accounts.role_table.has_role(&authority, Role::RegistryAdmin),
+            ErrorCode::UnauthorizedUser
+        );
+        require!(
+            meter_account.status != MeterStatus::Active,
+            ErrorCode::InvalidMeterStatus
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.meter_tombstone.set_inner(MeterTombstone {
+            owner: meter_account.owner,
+            decommissioned_at: now,
+        });
+        
+        let user_account = &mut ctx.accounts.user_account;
+        user_account.meter_count = user_account
+            .meter_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        let registry = &mut ctx.accounts.registry;
+        registry.meter_count = registry
+            .meter_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        // Release the meter's share of its topology node
+        if meter_account.grid_node != Pubkey::default() {
+            let grid_node = ctx
+                .accounts
+                .grid_node
+                .as_mut()
+                .ok_or(ErrorCode::InvalidGridNode)?;
+            grid_node.connected_capacity_w = grid_node
+                .connected_capacity_w
+                .saturating_sub(meter_account.rated_capacity_w);
+            grid_node.meter_count = grid_node.meter_count.saturating_sub(1);
+        }
+        
+        emit!(MeterDecommissioned {
+            meter_id: meter_account.meter_id.clone(),
+            owner: meter_account.owner,
+            authority,
+            total_generation: meter_account.total_generation,
+            total_consumption: meter_account.total_consumption,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Grant a registry role to a key (RegistryAdmin only)
+    pub fn grant_role(ctx: Context<ManageRole>, member: Pubkey, role: Role) -> Result<()> {
+        ctx.accounts.role_table.grant(member, role)?;
+        
+        emit!(RoleGranted {
+            authority: ctx.accounts.authority.key(),
+            member,
+            role,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Revoke a registry role from a key (RegistryAdmin only)
+    pub fn revoke_role(ctx: Context<ManageRole>, member: Pubkey, role: Role) -> Result<()> {
+        ctx.accounts.role_table.revoke(member, role)?;
+        
+        emit!(RoleRevoked {
+            authority: ctx.accounts.authority.key(),
+            member,
+            role,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Update meter reading (CPI from the oracle program only)
     pub fn update_meter_reading(
         ctx: Context<UpdateMeterReading>,
         energy_generated: u64,
@@ -129,10 +587,22 @@ pub mod registry {
     ) -> Result<()> {
         let meter_account = &mut ctx.accounts.meter_account;
         
-        // Update meter data
-        meter_account.last_reading_at = reading_timestamp;
-        meter_account.total_generation += energy_generated;
-        meter_account.total_consumption += energy_consumed;
+        // Meters in maintenance or taken out of service do not accrue readings
+        require!(
+            meter_account.status == MeterStatus::Active,
+            ErrorCode::InvalidMeterStatus
+        );
+        
+        // Update meter data; late (e.g. quarantine-approved) readings never move the clock back
+        meter_account.last_reading_at = meter_account.last_reading_at.max(reading_timestamp);
+        meter_account.total_generation = meter_account
+            .total_generation
+            .checked_add(energy_generated)
+            .ok_or(ErrorCode::MathOverflow)?;
+        meter_account.total_consumption = meter_account
+            .total_consumption
+            .checked_add(energy_consumed)
+            .ok_or(ErrorCode::MathOverflow)?;
         
         emit!(MeterReadingUpdated {
             meter_id: meter_account.meter_id.clone(),
@@ -145,6 +615,42 @@ pub mod registry {
         Ok(())
     }
     
+    /// Adjust a meter's totals after a reading is found wrong (CPI from the oracle program only)
+    pub fn correct_meter_reading(
+        ctx: Context<UpdateMeterReading>,
+        generation_delta: i64,
+        consumption_delta: i64,
+        reading_timestamp: i64,
+    ) -> Result<()> {
+        let meter_account = &mut ctx.accounts.meter_account;
+        
+        // Pending meters never took readings to correct
+        require!(
+            meter_account.status != MeterStatus::Pending,
+            ErrorCode::InvalidMeterStatus
+        );
+        
+        meter_account.total_generation = meter_account
+            .total_generation
+            .checked_add_signed(generation_delta)
+            .ok_or(ErrorCode::MathOverflow)?;
+        meter_account.total_consumption = meter_account
+            .total_consumption
+            .checked_add_signed(consumption_delta)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        emit!(MeterReadingCorrected {
+            meter_id: meter_account.meter_id.clone(),
+            owner: meter_account.owner,
+            generation_delta,
+            consumption_delta,
+            reading_timestamp,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
     /// Verify if a user is valid and active
     pub fn is_valid_user(ctx: Context<IsValidUser>) -> Result<bool> {
         let user_account = &ctx.accounts.user_account;
@@ -157,14 +663,298 @@ pub mod registry {
         Ok(meter_account.status == MeterStatus::Active)
     }
 
-    pub fn assign_meter(ctx: Context<AssignMeter>, meter_id: String) -> Result<()> {
+    /// Provision a meter for a registered user, e.g. before a dorm resident first logs in
+    /// (MeterInstaller or RegistryAdmin). The meter still needs validator activation.
+    pub fn assign_meter(
+        ctx: Context<AssignMeter>,
+        meter_id: String,
+        meter_type: MeterType,
+        rated_capacity_w: u64,
+        installation_profile: InstallationProfile,
+    ) -> Result<()> {
+        let user_account = &mut ctx.accounts.user_account;
+        require!(
+            user_account.status == UserStatus::Active,
+            ErrorCode::InvalidUserStatus
+        );
+        
+        let assignment = MeterAssignment {
+            meter_id,
+            owner: user_account.authority,
+            meter_type,
+            rated_capacity_w,
+            installation_profile,
+        };
+        assignment.validate()?;
+        
+        let grid_node = connect_to_grid_node(ctx.accounts.grid_node.as_mut(), rated_capacity_w)?;
+        let meter_account = &mut ctx.accounts.meter_account;
+        meter_account.set_inner(MeterAccount::pending(&assignment, grid_node, Clock::get()?.unix_timestamp));
+        
+        user_account.meter_count = user_account
+            .meter_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
         let registry = &mut ctx.accounts.registry;
-        registry.meter_count += 1;
-        msg!("Meter {} assigned", meter_id);
+        registry.meter_count = registry
+            .meter_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        emit_meter_registered(meter_account);
+        emit!(MeterAssigned {
+            meter_id: assignment.meter_id,
+            owner: assignment.owner,
+            installer: ctx.accounts.authority.key(),
+            timestamp: meter_account.registered_at,
+        });
+        
+        Ok(())
+    }
+    
+    /// Provision several meters in one transaction (MeterInstaller or RegistryAdmin)
+    ///
+    /// Remaining accounts hold one `[meter PDA, meter tombstone PDA, owner's user account]`
+    /// triple per assignment, in order. All meters connect to the same optional grid node.
+    pub fn assign_meters_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssignMetersBatch<'info>>,
+        assignments: Vec<MeterAssignment>,
+    ) -> Result<()> {
+        require!(
+            !assignments.is_empty() && assignments.len() <= MAX_BATCH_ASSIGNMENTS,
+            ErrorCode::InvalidMeterBatch
+        );
+        require!(
+            ctx.remaining_accounts.len() == assignments.len() * 3,
+            ErrorCode::InvalidMeterBatch
+        );
+        // Several meters may go to one owner, but each meter only once. Owners repeat
+        // safely: their account is reloaded and written back for every meter.
+        let mut meter_keys: Vec<Pubkey> = ctx.remaining_accounts.iter().step_by(3).map(|info| info.key()).collect();
+        meter_keys.sort();
+        require!(meter_keys.windows(2).all(|pair| pair[0] != pair[1]), ErrorCode::InvalidMeterBatch);
+        
+        let now = Clock::get()?.unix_timestamp;
+        
+        for (assignment, accounts) in assignments.iter().zip(ctx.remaining_accounts.chunks(3)) {
+            let (meter_info, tombstone_info, user_info) = (&accounts[0], &accounts[1], &accounts[2]);
+            assignment.validate()?;
+            
+            let mut user_account: Account<'info, UserAccount> = Account::try_from(user_info)?;
+            let (expected_user, _) = Pubkey::find_program_address(
+                &[b"user", assignment.owner.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(user_info.key(), expected_user, ErrorCode::InvalidMeterBatch);
+            require!(
+                user_account.status == UserStatus::Active,
+                ErrorCode::InvalidUserStatus
+            );
+            
+            let (expected_meter, bump) = Pubkey::find_program_address(
+                &[b"meter", assignment.meter_id.as_bytes()],
+                &crate::ID,
+            );
+            require_keys_eq!(meter_info.key(), expected_meter, ErrorCode::InvalidMeterBatch);
+            let (expected_tombstone, _) = Pubkey::find_program_address(
+                &[b"meter_tombstone", assignment.meter_id.as_bytes()],
+                &crate::ID,
+            );
+            require_keys_eq!(tombstone_info.key(), expected_tombstone, ErrorCode::InvalidMeterBatch);
+            require!(tombstone_info.data_is_empty(), ErrorCode::MeterDecommissioned);
+            
+            create_meter_account(
+                meter_info,
+                &ctx.accounts.authority,
+                &ctx.accounts.system_program,
+                &[b"meter", assignment.meter_id.as_bytes(), &[bump]],
+            )?;
+            
+            let grid_node = connect_to_grid_node(ctx.accounts.grid_node.as_mut(), assignment.rated_capacity_w)?;
+            let meter = MeterAccount::pending(assignment, grid_node, now);
+            meter.try_serialize(&mut &mut meter_info.try_borrow_mut_data()?[..])?;
+            
+            user_account.meter_count = user_account
+                .meter_count
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            user_account.exit(&crate::ID)?;
+            let registry = &mut ctx.accounts.registry;
+            registry.meter_count = registry
+                .meter_count
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            
+            emit_meter_registered(&meter);
+            emit!(MeterAssigned {
+                meter_id: assignment.meter_id.clone(),
+                owner: assignment.owner,
+                installer: ctx.accounts.authority.key(),
+                timestamp: now,
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Upgrade a `Registry`, `UserAccount` or `MeterAccount` to the current layout.
+    ///
+    /// Grows the account to the current size (the payer tops up rent), back-fills
+    /// fields added since its version and stamps `ACCOUNT_VERSION`. Permissionless,
+    /// since the upgrade is fully determined by the stored data.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let info = ctx.accounts.account.to_account_info();
+        let discriminator = info
+            .try_borrow_data()?
+            .get(..8)
+            .map(|d| d.to_vec())
+            .ok_or(ErrorCode::InvalidMigrationTarget)?;
+        
+        let payer = &ctx.accounts.payer;
+        let system_program = &ctx.accounts.system_program;
+        let from_version = if discriminator == Registry::DISCRIMINATOR {
+            rewrite_account(&info, payer, system_program, Registry::from_stored, Registry::migrate)?
+        } else if discriminator == UserAccount::DISCRIMINATOR {
+            rewrite_account(&info, payer, system_program, UserAccount::from_stored, UserAccount::migrate)?
+        } else if discriminator == MeterAccount::DISCRIMINATOR {
+            rewrite_account(&info, payer, system_program, MeterAccount::from_stored, MeterAccount::migrate)?
+        } else {
+            return err!(ErrorCode::InvalidMigrationTarget);
+        };
+        
+        emit!(AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: ACCOUNT_VERSION,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
         Ok(())
     }
 }
 
+/// Zero-extend an account to `space` bytes, funding the extra rent from `payer`
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+    
+    fund_rent(account, payer, system_program, space)?;
+    account.resize(space)?;
+    Ok(())
+}
+
+/// Allocate a new meter PDA signed by `seeds` and hand it to this program
+///
+/// Unlike `create_account`, this still works when lamports were sent to the address
+/// beforehand, so pre-funding a meter address cannot block provisioning.
+fn create_meter_account<'info>(
+    meter: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let space = 8 + MeterAccount::INIT_SPACE;
+    fund_rent(meter, payer, system_program, space)?;
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate { account_to_allocate: meter.clone() },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign { account_to_assign: meter.clone() },
+            &[seeds],
+        ),
+        &crate::ID,
+    )?;
+    Ok(())
+}
+
+/// Top `account` up from `payer` to the rent-exempt minimum for `space` bytes
+fn fund_rent<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<()> {
+    let shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    Ok(())
+}
+
+/// Decode an account from the layout it was stored in, apply `migrate`, grow it to the
+/// current size and write it back in the current layout
+fn rewrite_account<'info, T: AccountSerialize + Space>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    decode: impl FnOnce(&[u8]) -> Result<T>,
+    migrate: impl FnOnce(&mut T) -> Result<u8>,
+) -> Result<u8> {
+    let mut data = decode(&account.try_borrow_data()?)?;
+    let from_version = migrate(&mut data)?;
+    grow_account(account, payer, system_program, 8 + T::INIT_SPACE)?;
+    data.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+    Ok(from_version)
+}
+
+/// The stored fields after the discriminator, if `data` is `layout_space` bytes long.
+/// Every layout was allocated at exactly its size, so the length identifies it.
+fn stored_body(data: &[u8], layout_space: usize) -> Option<&[u8]> {
+    (data.len() == 8 + layout_space).then(|| &data[8..])
+}
+
+/// Attach a new meter to its topology node, within the node's capacity limit
+fn connect_to_grid_node(grid_node: Option<&mut Account<GridNode>>, rated_capacity_w: u64) -> Result<Pubkey> {
+    let Some(node) = grid_node else {
+        return Ok(Pubkey::default());
+    };
+    
+    let connected = node
+        .connected_capacity_w
+        .checked_add(rated_capacity_w)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(connected <= node.capacity_w, ErrorCode::GridNodeCapacityExceeded);
+    node.connected_capacity_w = connected;
+    node.meter_count += 1;
+    Ok(node.key())
+}
+
+fn emit_meter_registered(meter: &MeterAccount) {
+    emit!(MeterRegistered {
+        meter_id: meter.meter_id.clone(),
+        owner: meter.owner,
+        meter_type: meter.meter_type,
+        rated_capacity_w: meter.rated_capacity_w,
+        installation_profile: meter.installation_profile,
+        grid_node: meter.grid_node,
+        timestamp: meter.registered_at,
+    });
+}
+
 // Account structs
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -177,6 +967,15 @@ pub struct Initialize<'info> {
     )]
     pub registry: Account<'info, Registry>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleTable::INIT_SPACE,
+        seeds = [b"role_table"],
+        bump
+    )]
+    pub role_table: Account<'info, RoleTable>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -184,21 +983,75 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(user_type: UserType, location: String)]
-pub struct RegisterUser<'info> {
-    #[account(mut)]
-    pub registry: Account<'info, Registry>,
+pub struct ManageRole<'info> {
+    #[account(mut, seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
     
     #[account(
-        init,
-        payer = user_authority,
-        space = 8 + UserAccount::INIT_SPACE,
-        seeds = [b"user", user_authority.key().as_ref()],
-        bump
+        constraint = role_table.has_role(&authority.key(), Role::RegistryAdmin) @ ErrorCode::MissingRole
     )]
-    pub user_account: Account<'info, UserAccount>,
-    
-    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(node_id: String)]
+pub struct CreateGridNode<'info> {
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GridNode::INIT_SPACE,
+        seeds = [b"grid_node", node_id.as_bytes()],
+        bump
+    )]
+    pub grid_node: Account<'info, GridNode>,
+    
+    #[account(seeds = [b"grid_node", parent.node_id.as_bytes()], bump)]
+    pub parent: Option<Account<'info, GridNode>>,
+    
+    #[account(
+        mut,
+        constraint = role_table.has_role(&authority.key(), Role::RegistryAdmin) @ ErrorCode::MissingRole
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGridNodeCapacity<'info> {
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(mut, seeds = [b"grid_node", grid_node.node_id.as_bytes()], bump)]
+    pub grid_node: Account<'info, GridNode>,
+    
+    #[account(
+        constraint = role_table.has_role(&authority.key(), Role::RegistryAdmin) @ ErrorCode::MissingRole
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterUser<'info> {
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut, seeds = [b"grid_node", grid_node.node_id.as_bytes()], bump)]
+    pub grid_node: Option<Account<'info, GridNode>>,
+    
+    #[account(
+        init,
+        payer = user_authority,
+        space = 8 + UserAccount::INIT_SPACE,
+        seeds = [b"user", user_authority.key().as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(mut)]
     pub user_authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
@@ -220,7 +1073,25 @@ pub struct RegisterMeter<'info> {
         seeds = [b"meter", meter_id.as_bytes()],
         bump
     )]
-    pub meter_account: Account<'info, MeterAccount>,
+    pub meter_account: Account<'info, MeterAccount>,    
+    /// CHECK: Must not exist; decommissioned meter IDs are never registered again
+    #[account(
+        seeds = [b"meter_tombstone", meter_id.as_bytes()],
+        bump,
+        constraint = meter_tombstone.data_is_empty() @ ErrorCode::MeterDecommissioned
+    )]
+    pub meter_tombstone: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"grid_node", grid_node.node_id.as_bytes()], bump)]
+    pub grid_node: Option<Account<'info, GridNode>>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Global) @ ErrorCode::RegistryPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
     
     #[account(mut)]
     pub user_authority: Signer<'info>,
@@ -228,15 +1099,203 @@ pub struct RegisterMeter<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(identity_hash: [u8; 32])]
+pub struct AttestUser<'info> {
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(mut, seeds = [b"user", user_account.authority.as_ref()], bump)]
+    pub user_account: Account<'info, UserAccount>,
+    
+    /// Fails to initialize if the identity is already bound to another user
+    #[account(
+        init,
+        payer = attestor,
+        space = 8 + IdentityRecord::INIT_SPACE,
+        seeds = [b"identity", identity_hash.as_ref()],
+        bump
+    )]
+    pub identity_record: Account<'info, IdentityRecord>,
+    
+    #[account(
+        mut,
+        constraint = role_table.has_role(&attestor.key(), Role::IdentityAttestor) @ ErrorCode::MissingRole
+    )]
+    pub attestor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateUserStatus<'info> {
-    #[account(has_one = authority @ ErrorCode::UnauthorizedAuthority)]
-    pub registry: Account<'info, Registry>,
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(mut)]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        constraint = role_table.has_role(&authority.key(), Role::RegistryAdmin) @ ErrorCode::MissingRole
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferMeter<'info> {
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(mut, seeds = [b"meter", meter_account.meter_id.as_bytes()], bump)]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    #[account(mut, seeds = [b"user", meter_account.owner.as_ref()], bump)]
+    pub from_user_account: Account<'info, UserAccount>,
+    
+    #[account(mut, seeds = [b"user", to_user_account.authority.as_ref()], bump)]
+    pub to_user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Global) @ ErrorCode::RegistryPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    pub current_owner: Option<Signer<'info>>,
+    
+    pub new_owner: Option<Signer<'info>>,
+    
+    pub admin: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateMeter<'info> {
+    #[account(mut, seeds = [b"meter", meter_account.meter_id.as_bytes()], bump)]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"rec_validator", validator.key().as_ref()],
+        bump,
+        seeds::program = governance::ID,
+        constraint = rec_validator.is_active() @ ErrorCode::UnauthorizedValidator
+    )]
+    pub rec_validator: Account<'info, RecValidator>,
+    
+    /// Mutated by governance when the activation is counted as an attestation
+    #[account(mut, seeds = [b"poa_config"], bump, seeds::program = governance::ID)]
+    pub poa_config: Account<'info, PoAConfig>,
+    
+    /// CHECK: PDA signer governance accepts certification reports from
+    #[account(seeds = [b"registry_authority"], bump)]
+    pub registry_authority: UncheckedAccount<'info>,
+    
+    pub validator: Signer<'info>,
+    
+    pub governance_program: Program<'info, GovernanceProgram>,
+}
+
+#[derive(Accounts)]
+pub struct SetMeterStatus<'info> {
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(mut, seeds = [b"meter", meter_account.meter_id.as_bytes()], bump)]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    /// Required when a delegate of the owner changes the status
+    pub delegation: Option<Account<'info, Delegation>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey, meter: Pubkey)]
+pub struct CreateDelegation<'info> {
+    #[account(seeds = [b"user", owner.key().as_ref()], bump)]
+    pub user_account: Account<'info, UserAccount>,
+    
+    /// Required when delegating a single meter
+    #[account(seeds = [b"meter", meter_account.meter_id.as_bytes()], bump)]
+    pub meter_account: Option<Account<'info, MeterAccount>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [b"delegation", owner.key().as_ref(), delegate.as_ref(), meter.as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(mut, close = owner, has_one = owner @ ErrorCode::UnauthorizedUser)]
+    pub delegation: Account<'info, Delegation>,
     
     #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DecommissionMeter<'info> {
+    #[account(mut, seeds = [b"registry"], bump)]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(
+        mut,
+        seeds = [b"meter", meter_account.meter_id.as_bytes()],
+        bump,
+        has_one = owner @ ErrorCode::UnauthorizedUser,
+        close = owner
+    )]
+    pub meter_account: Account<'info, MeterAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MeterTombstone::INIT_SPACE,
+        seeds = [b"meter_tombstone", meter_account.meter_id.as_bytes()],
+        bump
+    )]
+    pub meter_tombstone: Account<'info, MeterTombstone>,
+    
+    #[account(mut, seeds = [b"user", meter_account.owner.as_ref()], bump)]
     pub user_account: Account<'info, UserAccount>,
     
+    #[account(
+        mut,
+        constraint = grid_node.key() == meter_account.grid_node @ ErrorCode::InvalidGridNode
+    )]
+    pub grid_node: Option<Account<'info, GridNode>>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Global) @ ErrorCode::RegistryPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// CHECK: Rent refund destination, must be the meter owner
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -244,6 +1303,16 @@ pub struct UpdateMeterReading<'info> {
     #[account(mut)]
     pub meter_account: Account<'info, MeterAccount>,
     
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::OracleIngest) @ ErrorCode::OracleIngestPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// Oracle program PDA; only the oracle can produce this signature via CPI
+    #[account(seeds = [b"oracle_authority"], bump, seeds::program = ORACLE_PROGRAM_ID)]
     pub oracle_authority: Signer<'info>,
 }
 
@@ -258,10 +1327,93 @@ pub struct IsValidMeter<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(meter_id: String)]
 pub struct AssignMeter<'info> {
+    #[account(mut, seeds = [b"registry"], bump)]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(mut, seeds = [b"user", user_account.authority.as_ref()], bump)]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MeterAccount::INIT_SPACE,
+        seeds = [b"meter", meter_id.as_bytes()],
+        bump
+    )]
+    pub meter_account: Account<'info, MeterAccount>,    
+    /// CHECK: Must not exist; decommissioned meter IDs are never registered again
+    #[account(
+        seeds = [b"meter_tombstone", meter_id.as_bytes()],
+        bump,
+        constraint = meter_tombstone.data_is_empty() @ ErrorCode::MeterDecommissioned
+    )]
+    pub meter_tombstone: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"grid_node", grid_node.node_id.as_bytes()], bump)]
+    pub grid_node: Option<Account<'info, GridNode>>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Global) @ ErrorCode::RegistryPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(
+        mut,
+        constraint = role_table.has_role(&authority.key(), Role::MeterInstaller)
+            || role_table.has_role(&authority.key(), Role::RegistryAdmin) @ ErrorCode::MissingRole
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: any registry-owned account; the discriminator selects the layout
+    #[account(mut, owner = crate::ID @ ErrorCode::InvalidMigrationTarget)]
+    pub account: UncheckedAccount<'info>,
+    
     #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AssignMetersBatch<'info> {
+    #[account(mut, seeds = [b"registry"], bump)]
     pub registry: Account<'info, Registry>,
+    
+    #[account(seeds = [b"role_table"], bump)]
+    pub role_table: Account<'info, RoleTable>,
+    
+    #[account(mut, seeds = [b"grid_node", grid_node.node_id.as_bytes()], bump)]
+    pub grid_node: Option<Account<'info, GridNode>>,
+    
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Global) @ ErrorCode::RegistryPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    #[account(
+        mut,
+        constraint = role_table.has_role(&authority.key(), Role::MeterInstaller)
+            || role_table.has_role(&authority.key(), Role::RegistryAdmin) @ ErrorCode::MissingRole
+    )]
     pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 // Data structs
@@ -272,6 +1424,91 @@ pub struct Registry {
     pub user_count: u64,
     pub meter_count: u64,
     pub created_at: i64,
+    pub version: u8,                 // Layout version, see `ACCOUNT_VERSION`
+}
+
+impl Registry {
+    /// Decode a registry stored in any layout version
+    pub fn from_stored(data: &[u8]) -> Result<Self> {
+        if let Some(mut body) = stored_body(data, RegistryV0::INIT_SPACE) {
+            let v0 = RegistryV0::deserialize(&mut body)?;
+            return Ok(Registry {
+                authority: v0.authority,
+                user_count: v0.user_count,
+                meter_count: v0.meter_count,
+                created_at: v0.created_at,
+                version: 0,
+            });
+        }
+        let mut body = stored_body(data, Registry::INIT_SPACE).ok_or(ErrorCode::InvalidMigrationTarget)?;
+        Ok(Registry::deserialize(&mut body)?)
+    }
+    
+    /// Bring a stale registry up to `ACCOUNT_VERSION`, returning its previous version
+    pub fn migrate(&mut self) -> Result<u8> {
+        let from_version = self.version;
+        require!(from_version < ACCOUNT_VERSION, ErrorCode::AccountAlreadyMigrated);
+        self.version = ACCOUNT_VERSION;
+        Ok(from_version)
+    }
+}
+
+/// Keys holding registry roles, each with a bitmask of `Role`s
+#[account]
+#[derive(InitSpace)]
+pub struct RoleTable {
+    #[max_len(MAX_ROLE_MEMBERS)]
+    pub members: Vec<RoleMember>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct RoleMember {
+    pub member: Pubkey,
+    pub roles: u8,
+}
+
+impl RoleTable {
+    pub fn has_role(&self, member: &Pubkey, role: Role) -> bool {
+        self.members
+            .iter()
+            .any(|m| m.member == *member && m.roles & role.bit() != 0)
+    }
+    
+    pub fn grant(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        require!(!role.is_retired(), ErrorCode::RoleRetired);
+        
+        if let Some(entry) = self.members.iter_mut().find(|m| m.member == member) {
+            entry.roles |= role.bit();
+            return Ok(());
+        }
+        
+        require!(
+            self.members.len() < MAX_ROLE_MEMBERS,
+            ErrorCode::RoleTableFull
+        );
+        self.members.push(RoleMember { member, roles: role.bit() });
+        Ok(())
+    }
+    
+    pub fn revoke(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        require!(self.has_role(&member, role), ErrorCode::MissingRole);
+        
+        // Never lock the registry out of administration
+        if role == Role::RegistryAdmin {
+            let admins = self
+                .members
+                .iter()
+                .filter(|m| m.roles & Role::RegistryAdmin.bit() != 0)
+                .count();
+            require!(admins > 1, ErrorCode::LastRegistryAdmin);
+        }
+        
+        for entry in self.members.iter_mut().filter(|m| m.member == member) {
+            entry.roles &= !role.bit();
+        }
+        self.members.retain(|m| m.roles != 0);
+        Ok(())
+    }
 }
 
 #[account]
@@ -279,18 +1516,279 @@ pub struct Registry {
 pub struct UserAccount {
     pub authority: Pubkey,
     pub user_type: UserType,
-    #[max_len(100)]
-    pub location: String,
+    pub grid_node: Pubkey,           // Topology node the user sits behind (default = unplaced)
     pub status: UserStatus,
     pub registered_at: i64,
     pub meter_count: u32,
     // Backward compatibility field
     pub created_at: i64,
+    pub version: u8,                 // Layout version, see `ACCOUNT_VERSION`
+    // Version 2
+    pub identity_hash: [u8; 32],     // Salted university ID hash (zero = not attested)
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+}
+
+impl UserAccount {
+    /// Whether the gateway has linked this user to a campus identity
+    pub fn is_attested(&self) -> bool {
+        self.identity_hash != [0u8; 32]
+    }
+    
+    /// Decode a user account stored in any layout version
+    pub fn from_stored(data: &[u8]) -> Result<Self> {
+        let (authority, user_type, grid_node, status, registered_at, meter_count, created_at, version) =
+            if let Some(mut body) = stored_body(data, UserAccountV0::INIT_SPACE) {
+                // The free-text location has no on-chain equivalent; the user starts unplaced
+                let v0 = UserAccountV0::deserialize(&mut body)?;
+                (v0.authority, v0.user_type, Pubkey::default(), v0.status, v0.registered_at, v0.meter_count, v0.created_at, 0)
+            } else if let Some(mut body) = stored_body(data, UserAccountV1::INIT_SPACE) {
+                let v1 = UserAccountV1::deserialize(&mut body)?;
+                (v1.authority, v1.user_type, v1.grid_node, v1.status, v1.registered_at, v1.meter_count, v1.created_at, v1.version)
+            } else {
+                let mut body = stored_body(data, UserAccount::INIT_SPACE).ok_or(ErrorCode::InvalidMigrationTarget)?;
+                return Ok(UserAccount::deserialize(&mut body)?);
+            };
+        
+        Ok(UserAccount {
+            authority,
+            user_type,
+            grid_node,
+            status,
+            registered_at,
+            meter_count,
+            created_at,
+            version,
+            identity_hash: [0u8; 32],
+            attested_by: Pubkey::default(),
+            attested_at: 0,
+        })
+    }
+    
+    /// Bring a stale user account up to `ACCOUNT_VERSION`, returning its previous version
+    pub fn migrate(&mut self) -> Result<u8> {
+        let from_version = self.version;
+        require!(from_version < ACCOUNT_VERSION, ErrorCode::AccountAlreadyMigrated);
+        
+        // Some pre-versioning accounts never set the compatibility timestamp
+        if from_version == 0 && self.created_at == 0 {
+            self.created_at = self.registered_at;
+        }
+        // Version 2 fields are zero-extended, i.e. not yet attested
+        self.version = ACCOUNT_VERSION;
+        Ok(from_version)
+    }
+}
+
+/// A meter to create and the user it belongs to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MeterAssignment {
+    pub meter_id: String,
+    pub owner: Pubkey,               // Authority of the owning user account
+    pub meter_type: MeterType,
+    pub rated_capacity_w: u64,
+    pub installation_profile: InstallationProfile,
+}
+
+impl MeterAssignment {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.meter_id.is_empty() && self.meter_id.len() <= 50,
+            ErrorCode::InvalidMeterId
+        );
+        require!(self.rated_capacity_w > 0, ErrorCode::InvalidRatedCapacity);
+        
+        // A grid connection point can never be a pure generator
+        require!(
+            !(self.meter_type == MeterType::Grid
+                && self.installation_profile == InstallationProfile::GenerationOnly),
+            ErrorCode::InvalidInstallationProfile
+        );
+        Ok(())
+    }
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct MeterAccount {
+    #[max_len(50)]
+    pub meter_id: String,
+    pub owner: Pubkey,
+    pub meter_type: MeterType,
+    pub rated_capacity_w: u64,       // Nameplate capacity in watts
+    pub installation_profile: InstallationProfile,
+    pub grid_node: Pubkey,           // Topology node the meter connects to (default = unplaced)
+    pub status: MeterStatus,
+    #[max_len(64)]
+    pub inspection_reference: String, // Installation inspection recorded at activation
+    pub activated_by: Pubkey,         // REC validator who approved the installation
+    pub activated_at: i64,
+    pub registered_at: i64,
+    pub last_reading_at: i64,
+    pub total_generation: u64,
+    pub total_consumption: u64,
+    pub version: u8,                  // Layout version, see `ACCOUNT_VERSION`
+}
+
+/// Left under a decommissioned meter's ID so the ID can't be registered again
+#[account]
+#[derive(InitSpace)]
+pub struct MeterTombstone {
+    pub owner: Pubkey,
+    pub decommissioned_at: i64,
+}
+
+/// Claims a campus identity for exactly one user
+#[account]
+#[derive(InitSpace)]
+pub struct IdentityRecord {
+    pub identity_hash: [u8; 32],
+    pub user: Pubkey,                // Authority of the attested user account
+    pub attested_at: i64,
+}
+
+/// Lets `delegate` act for a user on one meter or all of their meters
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    pub owner: Pubkey,               // Authority of the delegating user account
+    pub delegate: Pubkey,
+    pub meter: Pubkey,               // Meter account covered (default = all of the owner's meters)
+    pub scopes: u8,                  // Bitmask of `DelegationScope`s
+    pub expires_at: i64,             // 0 = until revoked
+    pub created_at: i64,
+}
+
+impl Delegation {
+    /// Whether `delegate` may act within `scope` on `meter` at `now`.
+    ///
+    /// Pass `Pubkey::default()` as `meter` for actions not tied to one meter, such as
+    /// trading, which only an all-meters delegation covers.
+    pub fn authorizes(&self, delegate: &Pubkey, meter: &Pubkey, scope: DelegationScope, now: i64) -> bool {
+        self.delegate == *delegate
+            && (self.meter == Pubkey::default() || self.meter == *meter)
+            && self.scopes & scope.bit() != 0
+            && (self.expires_at == 0 || now < self.expires_at)
+    }
+}
+
+/// A point in the campus distribution network
+#[account]
+#[derive(InitSpace)]
+pub struct GridNode {
+    #[max_len(32)]
+    pub node_id: String,
+    pub node_type: GridNodeType,
+    pub parent: Option<Pubkey>,
+    pub capacity_w: u64,             // Capacity limit of the node
+    pub connected_capacity_w: u64,   // Rated capacity of meters connected directly to this node
+    pub user_count: u32,
+    pub meter_count: u32,
+    pub created_at: i64,
+}
+
+impl MeterAccount {
+    /// Decode a meter stored in any layout version
+    pub fn from_stored(data: &[u8]) -> Result<Self> {
+        if let Some(mut body) = stored_body(data, MeterAccountV0::INIT_SPACE) {
+            let v0 = MeterAccountV0::deserialize(&mut body)?;
+            return Ok(MeterAccount {
+                meter_id: v0.meter_id,
+                owner: v0.owner,
+                meter_type: v0.meter_type,
+                rated_capacity_w: 0,
+                installation_profile: InstallationProfile::Bidirectional,
+                grid_node: Pubkey::default(),
+                status: v0.status,
+                inspection_reference: String::new(),
+                activated_by: Pubkey::default(),
+                activated_at: 0,
+                registered_at: v0.registered_at,
+                last_reading_at: v0.last_reading_at,
+                total_generation: v0.total_generation,
+                total_consumption: v0.total_consumption,
+                version: 0,
+            });
+        }
+        let mut body = stored_body(data, MeterAccount::INIT_SPACE).ok_or(ErrorCode::InvalidMigrationTarget)?;
+        Ok(MeterAccount::deserialize(&mut body)?)
+    }
+    
+    /// Bring a stale meter up to `ACCOUNT_VERSION`, returning its previous version
+    pub fn migrate(&mut self) -> Result<u8> {
+        let from_version = self.version;
+        require!(from_version < ACCOUNT_VERSION, ErrorCode::AccountAlreadyMigrated);
+        
+        // Pre-versioning meters never recorded their capacity, so they go back through
+        // inspection, where the validator records it
+        if from_version == 0 {
+            self.status = MeterStatus::Pending;
+        }
+        self.version = ACCOUNT_VERSION;
+        Ok(from_version)
+    }
+    
+    /// Newly registered meter awaiting validator activation
+    pub fn pending(assignment: &MeterAssignment, grid_node: Pubkey, now: i64) -> Self {
+        MeterAccount {
+            meter_id: assignment.meter_id.clone(),
+            owner: assignment.owner,
+            meter_type: assignment.meter_type,
+            rated_capacity_w: assignment.rated_capacity_w,
+            installation_profile: assignment.installation_profile,
+            grid_node,
+            status: MeterStatus::Pending,
+            inspection_reference: String::new(),
+            activated_by: Pubkey::default(),
+            activated_at: 0,
+            registered_at: now,
+            last_reading_at: 0,
+            total_generation: 0,
+            total_consumption: 0,
+            version: ACCOUNT_VERSION,
+        }
+    }
+}
+
+// Legacy layouts, frozen as they were stored, for `migrate_account`
+/// `Registry` before versioning
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct RegistryV0 {
+    pub authority: Pubkey,
+    pub user_count: u64,
+    pub meter_count: u64,
+    pub created_at: i64,
+}
+
+/// `UserAccount` before versioning
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct UserAccountV0 {
+    pub authority: Pubkey,
+    pub user_type: UserType,
+    #[max_len(100)]
+    pub location: String,
+    pub status: UserStatus,
+    pub registered_at: i64,
+    pub meter_count: u32,
+    pub created_at: i64,
+}
+
+/// `UserAccount` at version 1, before identity attestation
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct UserAccountV1 {
+    pub authority: Pubkey,
+    pub user_type: UserType,
+    pub grid_node: Pubkey,
+    pub status: UserStatus,
+    pub registered_at: i64,
+    pub meter_count: u32,
+    pub created_at: i64,
+    pub version: u8,
+}
+
+/// `MeterAccount` before versioning; version 1 is the current layout
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct MeterAccountV0 {
     #[max_len(50)]
     pub meter_id: String,
     pub owner: Pubkey,
@@ -303,6 +1801,61 @@ pub struct MeterAccount {
 }
 
 // Enums
+/// Registry roles; mirrors the gateway's `auth::Role` for on-chain actions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    RegistryAdmin,    // Manages users, topology and the role table
+    MeterInstaller,   // Facilities staff provisioning and servicing meters
+    Auditor,          // Retired: no instruction checks it; kept so stored roles decode
+    OracleService,    // Retired: readings are pinned to the oracle PDA; kept so stored roles decode
+    IdentityAttestor, // Gateway key vouching for campus identities
+}
+
+impl Role {
+    fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+    
+    /// Retired roles can still be revoked but no longer granted
+    fn is_retired(self) -> bool {
+        matches!(self, Role::Auditor | Role::OracleService)
+    }
+}
+
+/// What a delegate may do on the owner's behalf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DelegationScope {
+    MeterStatus,      // Take meters in and out of service
+    Trading,          // Place orders for the owner (all-meters delegations only)
+    ViewData,         // Read readings and statements through the gateway
+}
+
+impl DelegationScope {
+    fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GridNodeType {
+    Campus,
+    Building,
+    Feeder,
+    Transformer,
+}
+
+impl GridNodeType {
+    /// Node type one level up the hierarchy
+    pub fn parent_type(&self) -> Option<GridNodeType> {
+        match self {
+            GridNodeType::Campus => None,
+            GridNodeType::Building => Some(GridNodeType::Campus),
+            GridNodeType::Feeder => Some(GridNodeType::Building),
+            GridNodeType::Transformer => Some(GridNodeType::Feeder),
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum UserType {
     Prosumer,
@@ -324,11 +1877,20 @@ pub enum MeterType {
     Grid,
 }
 
+/// Direction(s) of energy flow a meter is physically able to record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum InstallationProfile {
+    GenerationOnly,
+    ConsumptionOnly,
+    Bidirectional,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum MeterStatus {
     Active,
     Inactive,
     Maintenance,
+    Pending,      // Registered, awaiting validator inspection
 }
 
 // Events
@@ -342,7 +1904,7 @@ pub struct RegistryInitialized {
 pub struct UserRegistered {
     pub user: Pubkey,
     pub user_type: UserType,
-    pub location: String,
+    pub grid_node: Pubkey,
     pub timestamp: i64,
 }
 
@@ -351,6 +1913,27 @@ pub struct MeterRegistered {
     pub meter_id: String,
     pub owner: Pubkey,
     pub meter_type: MeterType,
+    pub rated_capacity_w: u64,
+    pub installation_profile: InstallationProfile,
+    pub grid_node: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GridNodeCreated {
+    pub node: Pubkey,
+    pub node_id: String,
+    pub node_type: GridNodeType,
+    pub parent: Option<Pubkey>,
+    pub capacity_w: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GridNodeCapacityUpdated {
+    pub node: Pubkey,
+    pub old_capacity_w: u64,
+    pub new_capacity_w: u64,
     pub timestamp: i64,
 }
 
@@ -362,6 +1945,101 @@ pub struct UserStatusUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct UserAttested {
+    pub user: Pubkey,
+    pub identity_hash: [u8; 32],
+    pub attestor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationCreated {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub meter: Pubkey,
+    pub scopes: u8,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub meter: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterAssigned {
+    pub meter_id: String,
+    pub owner: Pubkey,
+    pub installer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterActivated {
+    pub meter_id: String,
+    pub owner: Pubkey,
+    pub validator: Pubkey,
+    pub inspection_reference: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterTransferred {
+    pub meter_id: String,
+    pub from_owner: Pubkey,
+    pub to_owner: Pubkey,
+    pub approved_by_admin: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterStatusUpdated {
+    pub meter_id: String,
+    pub authority: Pubkey,
+    pub old_status: MeterStatus,
+    pub new_status: MeterStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterDecommissioned {
+    pub meter_id: String,
+    pub owner: Pubkey,
+    pub authority: Pubkey,
+    pub total_generation: u64,
+    pub total_consumption: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub authority: Pubkey,
+    pub member: Pubkey,
+    pub role: Role,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub authority: Pubkey,
+    pub member: Pubkey,
+    pub role: Role,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MeterReadingUpdated {
     pub meter_id: String,
@@ -371,6 +2049,16 @@ pub struct MeterReadingUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct MeterReadingCorrected {
+    pub meter_id: String,
+    pub owner: Pubkey,
+    pub generation_delta: i64,
+    pub consumption_delta: i64,
+    pub reading_timestamp: i64,
+    pub timestamp: i64,
+}
+
 // Errors
 #[error_code]
 pub enum ErrorCode {
@@ -386,4 +2074,158 @@ pub enum ErrorCode {
     UserNotFound,
     #[msg("Meter not found")]
     MeterNotFound,
-}
\ No newline at end of file
+    #[msg("Rated capacity must be greater than zero")]
+    InvalidRatedCapacity,
+    #[msg("Installation profile is not valid for this meter type")]
+    InvalidInstallationProfile,
+    #[msg("Signer is not the registered oracle authority")]
+    UnauthorizedOracle,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Meter transfer needs both owners' signatures or RegistryAdmin approval")]
+    UnauthorizedMeterTransfer,
+    #[msg("Meter is already owned by this user")]
+    InvalidMeterTransfer,
+    #[msg("Signer is not an active REC validator")]
+    UnauthorizedValidator,
+    #[msg("Inspection reference must be 1-64 characters")]
+    InvalidInspectionReference,
+    #[msg("Invalid grid node")]
+    InvalidGridNode,
+    #[msg("Parent node must be exactly one level above in the grid topology")]
+    InvalidGridTopology,
+    #[msg("Grid node capacity exceeded")]
+    GridNodeCapacityExceeded,
+    #[msg("Signer does not hold the required registry role")]
+    MissingRole,
+    #[msg("Role table is full")]
+    RoleTableFull,
+    #[msg("Cannot revoke the last RegistryAdmin")]
+    LastRegistryAdmin,
+    #[msg("Meter id must be 1-50 characters")]
+    InvalidMeterId,
+    #[msg("Meter batch accounts do not match the assignments")]
+    InvalidMeterBatch,
+    #[msg("Account is not a versioned registry account")]
+    InvalidMigrationTarget,
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    #[msg("Invalid delegation: check delegate, meter, scopes and expiry")]
+    InvalidDelegation,
+    #[msg("Identity hash must not be empty")]
+    InvalidIdentityHash,
+    #[msg("User already has an attested identity")]
+    UserAlreadyAttested,
+    #[msg("Registry is paused")]
+    RegistryPaused,
+    #[msg("Oracle ingestion is paused")]
+    OracleIngestPaused,
+    #[msg("Meter ID belongs to a decommissioned meter")]
+    MeterDecommissioned,
+    #[msg("Role is retired and can no longer be granted")]
+    RoleRetired,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Stored bytes of an account: discriminator, fields, then zero padding to `space`
+    fn stored<T: AnchorSerialize>(discriminator: &[u8], fields: &T, space: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        fields.serialize(&mut data).unwrap();
+        data.resize(8 + space, 0);
+        data
+    }
+    
+    #[test]
+    fn test_migrates_v0_user_account() {
+        let authority = Pubkey::new_unique();
+        let v0 = UserAccountV0 {
+            authority,
+            user_type: UserType::Prosumer,
+            location: "Engineering Building, Floor 3".to_string(),
+            status: UserStatus::Suspended,
+            registered_at: 1_700_000_000,
+            meter_count: 2,
+            created_at: 0,
+        };
+        let data = stored(UserAccount::DISCRIMINATOR, &v0, UserAccountV0::INIT_SPACE);
+        
+        let mut user = UserAccount::from_stored(&data).unwrap();
+        assert_eq!(user.migrate().unwrap(), 0);
+        assert_eq!(user.authority, authority);
+        assert!(user.user_type == UserType::Prosumer);
+        assert!(user.status == UserStatus::Suspended);
+        assert_eq!(user.grid_node, Pubkey::default());
+        assert_eq!(user.meter_count, 2);
+        assert_eq!(user.created_at, 1_700_000_000, "back-filled from registered_at");
+        assert_eq!(user.version, ACCOUNT_VERSION);
+        assert!(!user.is_attested());
+    }
+    
+    #[test]
+    fn test_migrates_v1_user_account() {
+        let grid_node = Pubkey::new_unique();
+        let v1 = UserAccountV1 {
+            authority: Pubkey::new_unique(),
+            user_type: UserType::Consumer,
+            grid_node,
+            status: UserStatus::Active,
+            registered_at: 1_700_000_000,
+            meter_count: 1,
+            created_at: 1_700_000_001,
+            version: 1,
+        };
+        let data = stored(UserAccount::DISCRIMINATOR, &v1, UserAccountV1::INIT_SPACE);
+        
+        let mut user = UserAccount::from_stored(&data).unwrap();
+        assert_eq!(user.migrate().unwrap(), 1);
+        assert_eq!(user.grid_node, grid_node);
+        assert_eq!(user.created_at, 1_700_000_001);
+        assert_eq!(user.version, ACCOUNT_VERSION);
+    }
+    
+    #[test]
+    fn test_migrates_v0_meter_back_to_inspection() {
+        let v0 = MeterAccountV0 {
+            meter_id: "SOLAR-ENG-001".to_string(),
+            owner: Pubkey::new_unique(),
+            meter_type: MeterType::Solar,
+            status: MeterStatus::Active,
+            registered_at: 1_700_000_000,
+            last_reading_at: 1_700_003_600,
+            total_generation: 12_500,
+            total_consumption: 300,
+        };
+        let data = stored(MeterAccount::DISCRIMINATOR, &v0, MeterAccountV0::INIT_SPACE);
+        
+        let mut meter = MeterAccount::from_stored(&data).unwrap();
+        assert_eq!(meter.migrate().unwrap(), 0);
+        assert_eq!(meter.meter_id, "SOLAR-ENG-001");
+        assert_eq!(meter.total_generation, 12_500);
+        assert_eq!(meter.total_consumption, 300);
+        assert_eq!(meter.rated_capacity_w, 0);
+        assert!(meter.status == MeterStatus::Pending);
+        assert_eq!(meter.version, ACCOUNT_VERSION);
+    }
+    
+    #[test]
+    fn test_rejects_unknown_layouts_and_current_accounts() {
+        let v0 = RegistryV0 {
+            authority: Pubkey::new_unique(),
+            user_count: 4,
+            meter_count: 6,
+            created_at: 1_700_000_000,
+        };
+        let mut data = stored(Registry::DISCRIMINATOR, &v0, RegistryV0::INIT_SPACE);
+        let mut registry = Registry::from_stored(&data).unwrap();
+        assert_eq!(registry.migrate().unwrap(), 0);
+        assert_eq!(registry.meter_count, 6);
+        assert!(registry.migrate().is_err(), "already at the current version");
+        
+        data.push(0);
+        data.push(0);
+        assert!(Registry::from_stored(&data).is_err(), "size of no known layout");
+    }
+}
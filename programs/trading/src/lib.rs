@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use governance::{PauseConfig, PauseScope, ProtocolParams};
+use registry::{Delegation, DelegationScope, UserAccount, UserStatus};
 
 declare_id!("UbU6TWh6YP4kYQuj8t7xiNg65NdEQF9kfAKa4aS85iS");
 
@@ -20,7 +23,11 @@ pub mod trading {
         market.total_trades = 0;
         market.created_at = Clock::get()?.unix_timestamp;
         market.clearing_enabled = true;
-        market.market_fee_bps = 25; // 0.25% fee
+        market.clearing_authority = Pubkey::default();
+        market.min_price_per_kwh = 0;
+        market.max_price_per_kwh = 0;
+        market.price_bands_from = 0;
+        market.price_bands_until = 0;
         
         emit!(MarketInitialized {
             authority: ctx.accounts.authority.key(),
@@ -32,12 +39,21 @@ pub mod trading {
     
     /// Create a sell order for energy
     pub fn create_sell_order(
-        _ctx: Context<CreateSellOrder>,
+        ctx: Context<CreateSellOrder>,
         energy_amount: u64,
         price_per_kwh: u64,
     ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.market.price_in_bands(price_per_kwh, now),
+            ErrorCode::PriceOutOfBands
+        );
+        let trader = order_owner(&ctx.accounts.delegation, ctx.accounts.authority.key(), now)?;
+        require_attested_trader(&ctx.accounts.user_account, trader)?;
+        
         msg!(
-            "Creating sell order - Amount: {} kWh, Price: {} tokens/kWh",
+            "Creating sell order for {} - Amount: {} kWh, Price: {} tokens/kWh",
+            trader,
             energy_amount,
             price_per_kwh
         );
@@ -46,12 +62,21 @@ pub mod trading {
     
     /// Create a buy order for energy
     pub fn create_buy_order(
-        _ctx: Context<CreateBuyOrder>,
+        ctx: Context<CreateBuyOrder>,
         energy_amount: u64,
         max_price_per_kwh: u64,
     ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.market.price_in_bands(max_price_per_kwh, now),
+            ErrorCode::PriceOutOfBands
+        );
+        let trader = order_owner(&ctx.accounts.delegation, ctx.accounts.authority.key(), now)?;
+        require_attested_trader(&ctx.accounts.user_account, trader)?;
+        
         msg!(
-            "Creating buy order - Amount: {} kWh, Max Price: {} tokens/kWh",
+            "Creating buy order for {} - Amount: {} kWh, Max Price: {} tokens/kWh",
+            trader,
             energy_amount,
             max_price_per_kwh
         );
@@ -70,31 +95,352 @@ pub mod trading {
         Ok(())
     }
     
-    /// Update market parameters (admin only)
-    pub fn update_market_params(
-        ctx: Context<UpdateMarketParams>,
-        market_fee_bps: u16,
-        clearing_enabled: bool,
+    /// Set the oracle PDA allowed to run epoch auctions and publish price bands (admin only)
+    pub fn set_clearing_authority(
+        ctx: Context<SetClearingAuthority>,
+        clearing_authority: Pubkey,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        market.clearing_authority = clearing_authority;
+        
+        emit!(ClearingAuthorityUpdated {
+            authority: ctx.accounts.authority.key(),
+            clearing_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Hand market administration to a new authority, e.g. the governance PDA
+    pub fn transfer_market_authority(
+        ctx: Context<TransferMarketAuthority>,
+        new_authority: Pubkey,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
+        let old_authority = market.authority;
+        market.authority = new_authority;
         
+        emit!(MarketAuthorityTransferred {
+            old_authority,
+            new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Publish price bands derived from the oracle's grid tariff (CPI from the oracle only)
+    ///
+    /// Bands run from the lowest feed-in price to the highest import price: below the
+    /// floor a seller is better off exporting to the grid, above the ceiling a buyer is
+    /// better off importing from it.
+    pub fn update_price_bands(
+        ctx: Context<UpdatePriceBands>,
+        min_price_per_kwh: u64,
+        max_price_per_kwh: u64,
+        effective_from: i64,
+        effective_until: i64,
+    ) -> Result<()> {
+        require!(
+            min_price_per_kwh <= max_price_per_kwh,
+            ErrorCode::InvalidPriceBands
+        );
         require!(
-            ctx.accounts.authority.key() == market.authority,
-            ErrorCode::UnauthorizedAuthority
+            effective_until == 0 || effective_until > effective_from,
+            ErrorCode::InvalidPriceBands
         );
         
-        market.market_fee_bps = market_fee_bps;
-        market.clearing_enabled = clearing_enabled;
+        let market = &mut ctx.accounts.market;
+        market.min_price_per_kwh = min_price_per_kwh;
+        market.max_price_per_kwh = max_price_per_kwh;
+        market.price_bands_from = effective_from;
+        market.price_bands_until = effective_until;
+        
+        emit!(PriceBandsUpdated {
+            min_price_per_kwh,
+            max_price_per_kwh,
+            effective_from,
+            effective_until,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    /// Run the uniform-price auction for an epoch (CPI from the oracle only)
+    ///
+    /// Open orders are passed as writable remaining accounts, each at most once, followed
+    /// by one empty `[b"trade_record", epoch, fill]` address per expected fill. Orders
+    /// placed after the epoch ended or priced outside the current price bands are left
+    /// out. Bids are matched in descending and asks in ascending price order while they
+    /// cross; every fill settles at the midpoint of the last crossing pair and gets a
+    /// `TradeRecord` for settlement to pay out.
+    pub fn run_epoch_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RunEpochAuction<'info>>,
+        epoch: u64,
+    ) -> Result<()> {
+        require!(ctx.accounts.market.clearing_enabled, ErrorCode::ClearingDisabled);
+        
+        let now = Clock::get()?.unix_timestamp;
+        let params = &ctx.accounts.protocol_params.params;
+        let epoch_end = i64::try_from(epoch)
+            .ok()
+            .and_then(|e| e.checked_add(1))
+            .and_then(|e| e.checked_mul(params.epoch_duration_secs))
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        let (order_infos, record_slots): (Vec<_>, Vec<_>) =
+            ctx.remaining_accounts.iter().partition(|info| !info.data_is_empty());
+        
+        let mut order_keys: Vec<Pubkey> = order_infos.iter().map(|info| info.key()).collect();
+        order_keys.sort();
+        require!(
+            order_keys.windows(2).all(|pair| pair[0] != pair[1]),
+            ErrorCode::DuplicateOrder
+        );
+        
+        let mut bids: Vec<Account<'info, Order>> = Vec::new();
+        let mut asks: Vec<Account<'info, Order>> = Vec::new();
+        for info in order_infos {
+            let order: Account<'info, Order> = Account::try_from(info)?;
+            let open = matches!(order.status, OrderStatus::Active | OrderStatus::PartiallyFilled)
+                && order.created_at < epoch_end
+                && (order.expires_at == 0 || order.expires_at > now)
+                && order.filled_amount < order.amount
+                && ctx.accounts.market.price_in_bands(order.price_per_kwh, now);
+            if !open {
+                continue;
+            }
+            match order.order_type {
+                OrderType::Buy => bids.push(order),
+                OrderType::Sell => asks.push(order),
+            }
+        }
+        
+        bids.sort_by(|a, b| b.price_per_kwh.cmp(&a.price_per_kwh).then(a.created_at.cmp(&b.created_at)));
+        asks.sort_by(|a, b| a.price_per_kwh.cmp(&b.price_per_kwh).then(a.created_at.cmp(&b.created_at)));
+        
+        let (mut bid_idx, mut ask_idx) = (0, 0);
+        let mut cleared_volume: u64 = 0;
+        let mut fills: Vec<(usize, usize, u64)> = Vec::new();
+        let mut clearing_price: u64 = 0;
         
-        emit!(MarketParamsUpdated {
+        while bid_idx < bids.len() && ask_idx < asks.len() {
+            let bid_price = bids[bid_idx].price_per_kwh;
+            let ask_price = asks[ask_idx].price_per_kwh;
+            if bid_price < ask_price {
+                break;
+            }
+            
+            let bid_remaining = bids[bid_idx].amount - bids[bid_idx].filled_amount;
+            let ask_remaining = asks[ask_idx].amount - asks[ask_idx].filled_amount;
+            let quantity = bid_remaining.min(ask_remaining);
+            
+            bids[bid_idx].filled_amount += quantity;
+            asks[ask_idx].filled_amount += quantity;
+            cleared_volume = cleared_volume.checked_add(quantity).ok_or(ErrorCode::MathOverflow)?;
+            fills.push((bid_idx, ask_idx, quantity));
+            clearing_price = bid_price / 2 + ask_price / 2 + (bid_price % 2 + ask_price % 2) / 2;
+            
+            if bids[bid_idx].filled_amount == bids[bid_idx].amount {
+                bid_idx += 1;
+            }
+            if asks[ask_idx].filled_amount == asks[ask_idx].amount {
+                ask_idx += 1;
+            }
+        }
+        
+        // Fills are only priced once the auction has found its clearing price
+        require!(record_slots.len() >= fills.len(), ErrorCode::MissingTradeRecord);
+        for (index, (&(bid, ask, quantity), slot)) in fills.iter().zip(record_slots).enumerate() {
+            let total_value = quantity.checked_mul(clearing_price).ok_or(ErrorCode::MathOverflow)?;
+            let fee_amount = (total_value as u128 * params.market_fee_bps as u128 / 10_000) as u64;
+            let record = TradeRecord {
+                sell_order: asks[ask].key(),
+                buy_order: bids[bid].key(),
+                seller: asks[ask].seller,
+                buyer: bids[bid].buyer,
+                amount: quantity,
+                price_per_kwh: clearing_price,
+                total_value,
+                fee_amount,
+                executed_at: now,
+                refunded_amount: 0,
+            };
+            create_trade_record(
+                slot,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                epoch,
+                index as u64,
+                &record,
+            )?;
+        }
+        
+        for order in bids.iter_mut().chain(asks.iter_mut()) {
+            if order.filled_amount == order.amount {
+                order.status = OrderStatus::Completed;
+            } else if order.filled_amount > 0 {
+                order.status = OrderStatus::PartiallyFilled;
+            }
+            order.exit(&crate::ID)?;
+        }
+        
+        let fills = fills.len() as u64;
+        let market = &mut ctx.accounts.market;
+        market.total_volume = market.total_volume.saturating_add(cleared_volume);
+        market.total_trades = market.total_trades.saturating_add(fills);
+        
+        let epoch_auction = &mut ctx.accounts.epoch_auction;
+        epoch_auction.epoch = epoch;
+        epoch_auction.clearing_price = clearing_price;
+        epoch_auction.cleared_volume = cleared_volume;
+        epoch_auction.fill_count = fills;
+        epoch_auction.executed_at = now;
+        
+        emit!(EpochAuctionCompleted {
+            epoch,
+            clearing_price,
+            cleared_volume,
+            fill_count: fills,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    /// Turn epoch clearing on or off (governance only, through an executed proposal).
+    /// The market fee lives in the governance `ProtocolParams`.
+    pub fn set_clearing_enabled(ctx: Context<SetClearingEnabled>, clearing_enabled: bool) -> Result<()> {
+        ctx.accounts.market.clearing_enabled = clearing_enabled;
+        
+        emit!(ClearingStatusUpdated {
             authority: ctx.accounts.authority.key(),
-            market_fee_bps,
             clearing_enabled,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
+    
+    /// Refund part of a trade to its buyer after an upheld dispute (market authority,
+    /// i.e. governance, only)
+    ///
+    /// Pays the refund to the buyer in lamports from the market authority and records it
+    /// on the trade; refunds on one trade can never add up to more than its value.
+    pub fn refund_trade(ctx: Context<RefundTrade>, refund_amount: u64) -> Result<()> {
+        let trade_record = &mut ctx.accounts.trade_record;
+        let refunded_amount = trade_record
+            .refunded_amount
+            .checked_add(refund_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            refund_amount > 0 && refunded_amount <= trade_record.total_value,
+            ErrorCode::InvalidRefundAmount
+        );
+        trade_record.refunded_amount = refunded_amount;
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            refund_amount,
+        )?;
+        
+        emit!(TradeRefunded {
+            trade_record: trade_record.key(),
+            buyer: trade_record.buyer,
+            seller: trade_record.seller,
+            refund_amount,
+            refunded_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Key an order is placed for: the signer, or the user who delegated trading to it
+fn order_owner(delegation: &Option<Account<Delegation>>, authority: Pubkey, now: i64) -> Result<Pubkey> {
+    match delegation {
+        Some(delegation) => {
+            require!(
+                delegation.authorizes(&authority, &Pubkey::default(), DelegationScope::Trading, now),
+                ErrorCode::UnauthorizedDelegate
+            );
+            Ok(delegation.owner)
+        }
+        None => Ok(authority),
+    }
+}
+
+/// Only active users with an attested campus identity may trade
+fn require_attested_trader(user_account: &UserAccount, trader: Pubkey) -> Result<()> {
+    require_keys_eq!(user_account.authority, trader, ErrorCode::UnattestedTrader);
+    require!(
+        user_account.is_attested() && user_account.status == UserStatus::Active,
+        ErrorCode::UnattestedTrader
+    );
+    Ok(())
+}
+
+/// Write an auction fill to its `[b"trade_record", epoch, fill]` PDA
+///
+/// Allocates and assigns rather than `create_account`, so lamports sent to the
+/// address beforehand cannot block the auction.
+fn create_trade_record<'info>(
+    slot: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    epoch: u64,
+    fill: u64,
+    record: &TradeRecord,
+) -> Result<()> {
+    let epoch_bytes = epoch.to_le_bytes();
+    let fill_bytes = fill.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"trade_record", &epoch_bytes, &fill_bytes],
+        &crate::ID,
+    );
+    require_keys_eq!(slot.key(), expected, ErrorCode::MissingTradeRecord);
+    let signer_seeds: &[&[u8]] = &[b"trade_record", &epoch_bytes, &fill_bytes, &[bump]];
+
+    let space = 8 + TradeRecord::INIT_SPACE;
+    let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(slot.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer { from: payer.to_account_info(), to: slot.clone() },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate { account_to_allocate: slot.clone() },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign { account_to_assign: slot.clone() },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )?;
+
+    let mut data = slot.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+    Ok(())
 }
 
 // Account structs
@@ -125,6 +471,20 @@ pub struct CreateSellOrder<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
     
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Trading) @ ErrorCode::TradingPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// Present when a delegate trades on the owner's behalf
+    pub delegation: Option<Account<'info, Delegation>>,
+    
+    /// Registry account of the trader (the delegating owner, if any)
+    pub user_account: Account<'info, UserAccount>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -136,6 +496,20 @@ pub struct CreateBuyOrder<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
     
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Trading) @ ErrorCode::TradingPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
+    /// Present when a delegate trades on the owner's behalf
+    pub delegation: Option<Account<'info, Delegation>>,
+    
+    /// Registry account of the trader (the delegating owner, if any)
+    pub user_account: Account<'info, UserAccount>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -147,6 +521,14 @@ pub struct MatchOrders<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
     
+    #[account(
+        seeds = [b"pause_config"],
+        bump,
+        seeds::program = governance::ID,
+        constraint = !pause_config.is_paused(PauseScope::Trading) @ ErrorCode::TradingPaused
+    )]
+    pub pause_config: Account<'info, PauseConfig>,
+    
     pub authority: Signer<'info>,
 }
 
@@ -159,13 +541,92 @@ pub struct CancelOrder<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdateMarketParams<'info> {
+pub struct TransferMarketAuthority<'info> {
     #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority)]
     pub market: Account<'info, Market>,
     
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SetClearingAuthority<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority)]
+    pub market: Account<'info, Market>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePriceBands<'info> {
+    #[account(mut, seeds = [b"market"], bump)]
+    pub market: Account<'info, Market>,
+    
+    /// Oracle program PDA; only the oracle can produce this signature via CPI
+    #[account(
+        constraint = clearing_authority.key() == market.clearing_authority @ ErrorCode::UnauthorizedClearingAuthority
+    )]
+    pub clearing_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct RunEpochAuction<'info> {
+    #[account(mut, seeds = [b"market"], bump)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EpochAuction::INIT_SPACE,
+        seeds = [b"epoch_auction".as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub epoch_auction: Account<'info, EpochAuction>,
+    
+    #[account(seeds = [b"protocol_params"], bump, seeds::program = governance::ID)]
+    pub protocol_params: Account<'info, ProtocolParams>,
+    
+    /// Oracle program PDA; only the oracle can produce this signature via CPI
+    #[account(
+        constraint = clearing_authority.key() == market.clearing_authority @ ErrorCode::UnauthorizedClearingAuthority
+    )]
+    pub clearing_authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetClearingEnabled<'info> {
+    #[account(mut, seeds = [b"market"], bump)]
+    pub market: Account<'info, Market>,
+    
+    /// Governance authority PDA; only an executed proposal can produce this signature
+    #[account(seeds = [b"governance_authority"], bump, seeds::program = governance::ID)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundTrade<'info> {
+    #[account(has_one = authority @ ErrorCode::UnauthorizedAuthority)]
+    pub market: Account<'info, Market>,
+    
+    /// Pays the refund; governance's authority PDA when resolving a dispute
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub trade_record: Account<'info, TradeRecord>,
+    
+    /// CHECK: Receives the refund; must be the trade's buyer
+    #[account(mut, address = trade_record.buyer @ ErrorCode::InvalidRefundAmount)]
+    pub buyer: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 // Data structs
 #[account]
 #[derive(InitSpace)]
@@ -176,7 +637,33 @@ pub struct Market {
     pub total_trades: u64,
     pub created_at: i64,
     pub clearing_enabled: bool,
-    pub market_fee_bps: u16,
+    pub clearing_authority: Pubkey,  // Oracle PDA allowed to run epoch auctions and publish price bands
+    pub min_price_per_kwh: u64,      // Price floor from the grid feed-in tariff
+    pub max_price_per_kwh: u64,      // Price ceiling from the grid import tariff
+    pub price_bands_from: i64,
+    pub price_bands_until: i64,      // 0 = open-ended
+}
+
+impl Market {
+    /// Whether `price` is allowed at `now`; without published bands every price is
+    pub fn price_in_bands(&self, price: u64, now: i64) -> bool {
+        let bands_active = self.max_price_per_kwh > 0
+            && now >= self.price_bands_from
+            && (self.price_bands_until == 0 || now < self.price_bands_until);
+        
+        !bands_active || (price >= self.min_price_per_kwh && price <= self.max_price_per_kwh)
+    }
+}
+
+/// Outcome of one epoch's auction
+#[account]
+#[derive(InitSpace)]
+pub struct EpochAuction {
+    pub epoch: u64,
+    pub clearing_price: u64,
+    pub cleared_volume: u64,
+    pub fill_count: u64,
+    pub executed_at: i64,
 }
 
 #[account]
@@ -205,6 +692,7 @@ pub struct TradeRecord {
     pub total_value: u64,
     pub fee_amount: u64,
     pub executed_at: i64,
+    pub refunded_amount: u64,        // Refunded to the buyer through upheld disputes
 }
 
 // Enums
@@ -269,13 +757,54 @@ pub struct OrderCancelled {
 }
 
 #[event]
-pub struct MarketParamsUpdated {
+pub struct MarketAuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClearingAuthorityUpdated {
+    pub authority: Pubkey,
+    pub clearing_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceBandsUpdated {
+    pub min_price_per_kwh: u64,
+    pub max_price_per_kwh: u64,
+    pub effective_from: i64,
+    pub effective_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpochAuctionCompleted {
+    pub epoch: u64,
+    pub clearing_price: u64,
+    pub cleared_volume: u64,
+    pub fill_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClearingStatusUpdated {
     pub authority: Pubkey,
-    pub market_fee_bps: u16,
     pub clearing_enabled: bool,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TradeRefunded {
+    pub trade_record: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub refund_amount: u64,
+    pub refunded_amount: u64,
+    pub timestamp: i64,
+}
+
 // Errors
 #[error_code]
 pub enum ErrorCode {
@@ -295,4 +824,26 @@ pub enum ErrorCode {
     OrderNotCancellable,
     #[msg("Insufficient escrow balance")]
     InsufficientEscrowBalance,
+    #[msg("Market clearing is disabled")]
+    ClearingDisabled,
+    #[msg("Signer is not the registered clearing authority")]
+    UnauthorizedClearingAuthority,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Price is outside the tariff price bands")]
+    PriceOutOfBands,
+    #[msg("Invalid price bands")]
+    InvalidPriceBands,
+    #[msg("Delegation does not allow this signer to trade")]
+    UnauthorizedDelegate,
+    #[msg("Trader has no attested campus identity")]
+    UnattestedTrader,
+    #[msg("Trading is paused")]
+    TradingPaused,
+    #[msg("Refund must be positive and keep total refunds within the trade value")]
+    InvalidRefundAmount,
+    #[msg("An order was passed to the auction more than once")]
+    DuplicateOrder,
+    #[msg("Auction needs an empty trade record address for every fill")]
+    MissingTradeRecord,
 }
\ No newline at end of file
@@ -0,0 +1,153 @@
+/**
+ * Hand the upgrade authority of every program to governance and register each
+ * one with `governance::register_program`, so upgrades can only happen through
+ * an `UpgradeProgram` proposal.
+ *
+ * Run with the deployer wallet, which must be the current upgrade authority and
+ * a university authority signer, after `anchor build && anchor deploy`:
+ *
+ *   anchor run govern-upgrades                          # hand over and register
+ *   anchor run govern-upgrades -- --hash-buffer <addr>  # hash to name in an upgrade proposal
+ *
+ * To upgrade later, write the new binary with `solana program write-buffer`,
+ * hand the buffer to the governance authority PDA with `solana program
+ * set-buffer-authority`, and propose `UpgradeProgram` with the buffer and its hash.
+ */
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { createHash } from "crypto";
+import { readFileSync } from "fs";
+import * as path from "path";
+
+const LOADER_ID = new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/** Header sizes from `UpgradeableLoaderState::size_of_buffer_metadata` / `size_of_programdata_metadata` */
+const BUFFER_METADATA_LEN = 37;
+const PROGRAM_DATA_METADATA_LEN = 45;
+
+/** Workspace programs and their build artifacts in target/deploy */
+export const GOVERNED_PROGRAMS = [
+  { workspace: "Registry", binary: "registry.so" },
+  { workspace: "EnergyToken", binary: "energy_token.so" },
+  { workspace: "Oracle", binary: "oracle.so" },
+  { workspace: "Trading", binary: "trading.so" },
+  { workspace: "Governance", binary: "governance.so" },
+] as const;
+
+function sha256(data: Buffer): number[] {
+  return Array.from(createHash("sha256").update(data).digest());
+}
+
+/** The hash `execute_proposal` checks a buffer against before upgrading from it */
+export async function bufferHash(
+  connection: anchor.web3.Connection,
+  buffer: anchor.web3.PublicKey
+): Promise<number[]> {
+  const account = await connection.getAccountInfo(buffer);
+  if (!account || !account.owner.equals(LOADER_ID)) {
+    throw new Error(`${buffer.toBase58()} is not an upgradeable loader buffer`);
+  }
+  return sha256(account.data.subarray(BUFFER_METADATA_LEN));
+}
+
+function programDataAddress(programId: anchor.web3.PublicKey): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync([programId.toBuffer()], LOADER_ID)[0];
+}
+
+/** Loader `SetAuthority`; the new authority is a PDA, so it cannot co-sign the checked variant */
+function setUpgradeAuthorityInstruction(
+  programData: anchor.web3.PublicKey,
+  currentAuthority: anchor.web3.PublicKey,
+  newAuthority: anchor.web3.PublicKey
+): anchor.web3.TransactionInstruction {
+  return new anchor.web3.TransactionInstruction({
+    programId: LOADER_ID,
+    keys: [
+      { pubkey: programData, isSigner: false, isWritable: true },
+      { pubkey: currentAuthority, isSigner: true, isWritable: false },
+      { pubkey: newAuthority, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from([4, 0, 0, 0]),
+  });
+}
+
+export async function governProgram(
+  governanceProgram: Program<any>,
+  program: Program<any>,
+  binaryPath: string
+): Promise<void> {
+  const provider = governanceProgram.provider as anchor.AnchorProvider;
+  const [governanceAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("governance_authority")],
+    governanceProgram.programId
+  );
+  const programData = programDataAddress(program.programId);
+
+  // Make sure the binary being registered is the one deployed
+  const binary = readFileSync(binaryPath);
+  const account = await provider.connection.getAccountInfo(programData);
+  if (!account) {
+    throw new Error(`${program.programId.toBase58()} is not an upgradeable program`);
+  }
+  const deployed = account.data.subarray(PROGRAM_DATA_METADATA_LEN, PROGRAM_DATA_METADATA_LEN + binary.length);
+  if (!deployed.equals(binary)) {
+    throw new Error(`${binaryPath} does not match the code deployed at ${program.programId.toBase58()}`);
+  }
+
+  // Option<Pubkey> after the 4-byte tag and 8-byte slot
+  const authority = account.data[12] === 1 ? new anchor.web3.PublicKey(account.data.subarray(13, 45)) : null;
+  if (authority && !authority.equals(governanceAuthority)) {
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        setUpgradeAuthorityInstruction(programData, provider.wallet.publicKey, governanceAuthority)
+      )
+    );
+  }
+
+  const [programRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("program_record"), program.programId.toBuffer()],
+    governanceProgram.programId
+  );
+  if (await provider.connection.getAccountInfo(programRecord)) {
+    return;
+  }
+
+  await governanceProgram.methods
+    .registerProgram(sha256(binary))
+    .accounts({
+      programRecord,
+      program: program.programId,
+      programData,
+      governanceAuthority,
+      universityAuthority: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+}
+
+async function main() {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const hashArg = process.argv.indexOf("--hash-buffer");
+  if (hashArg !== -1) {
+    const buffer = new anchor.web3.PublicKey(process.argv[hashArg + 1]);
+    const hash = await bufferHash(provider.connection, buffer);
+    console.log(Buffer.from(hash).toString("hex"));
+    return;
+  }
+
+  const governanceProgram = anchor.workspace.Governance as Program<any>;
+  for (const { workspace, binary } of GOVERNED_PROGRAMS) {
+    const program = anchor.workspace[workspace] as Program<any>;
+    await governProgram(governanceProgram, program, path.join("target", "deploy", binary));
+    console.log(`✅ ${workspace} ${program.programId.toBase58()} is upgradeable only through governance`);
+  }
+}
+
+if (require.main === module) {
+  main().catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
+}
@@ -0,0 +1,115 @@
+/**
+ * Find registry accounts stored in an older layout and upgrade them with
+ * `registry::migrate_account`.
+ *
+ * An account is stale when it is smaller than the current layout, fails to
+ * decode with the current IDL, or carries a version below the IDL's ACCOUNT_VERSION.
+ *
+ *   anchor run migrate-registry              # migrate everything stale
+ *   anchor run migrate-registry -- --dry-run # only list stale accounts
+ */
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+
+/** `ACCOUNT_VERSION` from programs/registry/src/lib.rs, exported through the IDL */
+export function accountVersion(program: Program<any>): number {
+  const constant = (program.idl.constants ?? []).find(
+    (c: { name: string }) => c.name === "ACCOUNT_VERSION" || c.name === "accountVersion"
+  );
+  if (!constant) {
+    throw new Error("Registry IDL does not export ACCOUNT_VERSION; rebuild it with `anchor build`");
+  }
+  return Number(constant.value);
+}
+
+/** Versioned registry account types, as named in the IDL */
+export const VERSIONED_ACCOUNTS = ["Registry", "UserAccount", "MeterAccount"] as const;
+
+/** migrate_account is small, so several fit in one transaction */
+const MIGRATIONS_PER_TRANSACTION = 8;
+
+export interface StaleAccount {
+  address: anchor.web3.PublicKey;
+  accountType: (typeof VERSIONED_ACCOUNTS)[number];
+  version: number;
+}
+
+export async function findStaleAccounts(program: Program<any>): Promise<StaleAccount[]> {
+  const currentVersion = accountVersion(program);
+  const stale: StaleAccount[] = [];
+
+  for (const accountType of VERSIONED_ACCOUNTS) {
+    const client = program.account[accountType.charAt(0).toLowerCase() + accountType.slice(1)];
+    const accounts = await program.provider.connection.getProgramAccounts(program.programId, {
+      filters: [{ memcmp: program.coder.accounts.memcmp(accountType) }],
+    });
+
+    for (const { pubkey, account } of accounts) {
+      let version = 0;
+      try {
+        version = program.coder.accounts.decode(accountType, account.data).version ?? 0;
+      } catch {
+        // Too short for the current layout, so it predates versioning
+      }
+
+      if (account.data.length < client.size || version < currentVersion) {
+        stale.push({ address: pubkey, accountType, version });
+      }
+    }
+  }
+
+  return stale;
+}
+
+export async function migrateStaleAccounts(
+  program: Program<any>,
+  stale: StaleAccount[]
+): Promise<string[]> {
+  const provider = program.provider as anchor.AnchorProvider;
+  const signatures: string[] = [];
+
+  for (let i = 0; i < stale.length; i += MIGRATIONS_PER_TRANSACTION) {
+    const tx = new anchor.web3.Transaction();
+    for (const { address } of stale.slice(i, i + MIGRATIONS_PER_TRANSACTION)) {
+      tx.add(
+        await program.methods
+          .migrateAccount()
+          .accounts({
+            account: address,
+            payer: provider.wallet.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .instruction()
+      );
+    }
+    signatures.push(await provider.sendAndConfirm(tx));
+  }
+
+  return signatures;
+}
+
+async function main() {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const registryProgram = anchor.workspace.Registry as Program<any>;
+
+  const stale = await findStaleAccounts(registryProgram);
+  for (const { address, accountType, version } of stale) {
+    console.log(`${accountType} ${address.toBase58()} at version ${version}`);
+  }
+  console.log(`Found ${stale.length} stale registry accounts`);
+
+  if (stale.length === 0 || process.argv.includes("--dry-run")) {
+    return;
+  }
+
+  const signatures = await migrateStaleAccounts(registryProgram, stale);
+  console.log(`✅ Migrated ${stale.length} accounts in ${signatures.length} transactions`);
+}
+
+if (require.main === module) {
+  main().catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
+}
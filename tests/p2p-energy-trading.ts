@@ -22,6 +22,7 @@ describe("p2p-energy-trading", () => {
   // Generate keypairs for testing
   const authority = (provider.wallet as any).payer as Keypair;
   const apiGateway = Keypair.generate();
+  const guardian = Keypair.generate();
 
   it("Initializes all programs!", async () => {
     console.log("Starting P2P Energy Trading System Tests");
@@ -56,7 +57,7 @@ describe("p2p-energy-trading", () => {
       console.log("✅ Energy Token initialized:", energyTokenTx);
 
       // Test Oracle Program
-      const oracleTx = await oracleProgram.methods.initialize(apiGateway.publicKey)
+      const oracleTx = await oracleProgram.methods.initialize()
         .rpc();
       console.log("✅ Oracle initialized:", oracleTx);
 
@@ -65,11 +66,37 @@ describe("p2p-energy-trading", () => {
         .rpc();
       console.log("✅ Trading initialized:", tradingTx);
 
-      // Test Governance Program
-      const governanceTx = await governanceProgram.methods.initialize()
+      // Test Governance Program; only the upgrade authority (the deployer) can initialize it
+      const [programDataPda] = PublicKey.findProgramAddressSync(
+        [governanceProgram.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+      const governanceTx = await governanceProgram.methods.initializePoaWithRec({
+          marketFeeBps: 25,
+          minRecValidators: 2,
+          oracleReporter: apiGateway.publicKey,
+          epochDurationSecs: new anchor.BN(3_600),
+          minClearingIntervalSecs: new anchor.BN(300),
+          maxStalenessSecs: new anchor.BN(1_800),
+          minPricePerKwh: new anchor.BN(0),
+          maxPricePerKwh: new anchor.BN(0),
+        })
+        .accounts({
+          universityAuthority: authority.publicKey,
+          program: governanceProgram.programId,
+          programData: programDataPda,
+          sustainabilityValidator: Keypair.generate().publicKey,
+          engineeringValidator: Keypair.generate().publicKey,
+          facilitiesValidator: Keypair.generate().publicKey,
+        } as any)
         .rpc();
       console.log("✅ Governance initialized:", governanceTx);
 
+      // Pause flags read by every program; the guardian can pause but not unpause
+      const pauseTx = await governanceProgram.methods.initializePauseConfig(guardian.publicKey)
+        .rpc();
+      console.log("✅ Pause config initialized:", pauseTx);
+
       console.log("All P2P Energy Trading programs initialized successfully!");
     } catch (error) {
       console.error("❌ Error during initialization:", error);
@@ -18,6 +18,7 @@ describe("Performance Benchmarks", () => {
   const governanceProgram = anchor.workspace.Governance as Program<Governance>;
 
   let registryPda: anchor.web3.PublicKey;
+  let roleTablePda: anchor.web3.PublicKey;
   let marketPda: anchor.web3.PublicKey;
   let oracleConfigPda: anchor.web3.PublicKey;
 
@@ -27,17 +28,51 @@ describe("Performance Benchmarks", () => {
       registryProgram.programId
     );
 
+    [roleTablePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("role_table")],
+      registryProgram.programId
+    );
+
     [marketPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("market")],
       tradingProgram.programId
     );
 
+    // The test wallet stands in for the gateway when attesting traders
+    await registryProgram.methods
+      .grantRole(provider.wallet.publicKey, { identityAttestor: {} })
+      .accounts({
+        roleTable: roleTablePda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
     [oracleConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("oracle_config")],
       oracleProgram.programId
     );
   });
 
+  // Only attested users may trade; a random hash stands in for a university ID
+  const attestUser = async (userAccountPda: anchor.web3.PublicKey) => {
+    const identityHash = anchor.web3.Keypair.generate().publicKey.toBuffer();
+    const [identityRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("identity"), identityHash],
+      registryProgram.programId
+    );
+
+    await registryProgram.methods
+      .attestUser([...identityHash])
+      .accounts({
+        roleTable: roleTablePda,
+        userAccount: userAccountPda,
+        identityRecord: identityRecordPda,
+        attestor: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  };
+
   describe("Throughput Benchmarks", () => {
     it("Should handle high-frequency user registration", async () => {
       const startTime = Date.now();
@@ -60,8 +95,7 @@ describe("Performance Benchmarks", () => {
 
         const registrationPromise = registryProgram.methods
           .registerUser(
-            i % 2 === 0 ? { prosumer: {} } : { consumer: {} },
-            `Performance Test Building ${i}`
+            i % 2 === 0 ? { prosumer: {} } : { consumer: {} }
           )
           .accounts({
             registry: registryPda,
@@ -113,7 +147,7 @@ describe("Performance Benchmarks", () => {
 
         // First register the user
         await registryProgram.methods
-          .registerUser({ prosumer: {} }, `Trader Building ${i}`)
+          .registerUser({ prosumer: {} })
           .accounts({
             registry: registryPda,
             userAccount: userAccountPda,
@@ -122,6 +156,7 @@ describe("Performance Benchmarks", () => {
           })
           .signers([trader])
           .rpc();
+        await attestUser(userAccountPda);
 
         // Then create buy order (using createBuyOrder instead of createOrder)
         const orderPromise = tradingProgram.methods
@@ -131,6 +166,7 @@ describe("Performance Benchmarks", () => {
           )
           .accounts({
             market: marketPda,
+            userAccount: userAccountPda,
             authority: trader.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
@@ -198,8 +234,11 @@ describe("Performance Benchmarks", () => {
     it("Should measure market clearing performance", async () => {
       const startTime = Date.now();
 
+      // Clear the most recently completed hourly epoch
+      const previousEpoch = Math.floor(Date.now() / 1000 / 3600) - 1;
+
       await oracleProgram.methods
-        .triggerMarketClearing()
+        .triggerMarketClearing(new anchor.BN(previousEpoch))
         .accounts({
           oracleData: oracleConfigPda,
           authority: provider.wallet.publicKey,
@@ -246,8 +285,7 @@ describe("Performance Benchmarks", () => {
 
           const userPromise = registryProgram.methods
             .registerUser(
-              Math.random() > 0.3 ? { consumer: {} } : { prosumer: {} }, // 70% consumers, 30% prosumers
-              `${dept} Department - User ${i}`
+              Math.random() > 0.3 ? { consumer: {} } : { prosumer: {} } // 70% consumers, 30% prosumers
             )
             .accounts({
               registry: registryPda,
@@ -302,7 +340,7 @@ describe("Performance Benchmarks", () => {
 
       // User registration
       await registryProgram.methods
-        .registerUser({ prosumer: {} }, "Cost Measurement Building")
+        .registerUser({ prosumer: {} })
         .accounts({
           registry: registryPda,
           userAccount: userAccountPda,
@@ -311,6 +349,7 @@ describe("Performance Benchmarks", () => {
         })
         .signers([user])
         .rpc();
+      await attestUser(userAccountPda);
 
       // Order creation (using createSellOrder)
       await tradingProgram.methods
@@ -320,6 +359,7 @@ describe("Performance Benchmarks", () => {
         )
         .accounts({
           market: marketPda,
+          userAccount: userAccountPda,
           authority: user.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
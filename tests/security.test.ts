@@ -14,10 +14,12 @@ describe("Security Audit Tests", () => {
   const governanceProgram = anchor.workspace.Governance as Program<any>;
 
   let registryPda: anchor.web3.PublicKey;
+  let roleTablePda: anchor.web3.PublicKey;
   let tokenInfoPda: anchor.web3.PublicKey;
   let marketPda: anchor.web3.PublicKey;
   let oracleConfigPda: anchor.web3.PublicKey;
   let poaConfigPda: anchor.web3.PublicKey;
+  let pauseConfigPda: anchor.web3.PublicKey;
 
   before(async () => {
     [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -25,6 +27,11 @@ describe("Security Audit Tests", () => {
       registryProgram.programId
     );
 
+    [roleTablePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("role_table")],
+      registryProgram.programId
+    );
+
     [tokenInfoPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("token_info")],
       energyTokenProgram.programId
@@ -44,6 +51,11 @@ describe("Security Audit Tests", () => {
       [Buffer.from("poa_config")],
       governanceProgram.programId
     );
+
+    [pauseConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pause_config")],
+      governanceProgram.programId
+    );
   });
 
   describe("Authority and Permission Tests", () => {
@@ -95,9 +107,10 @@ describe("Security Audit Tests", () => {
       
       try {
         await governanceProgram.methods
-          .emergencyPause()
+          .emergencyPause({ global: {} })
           .accounts({
             poaConfig: poaConfigPda,
+            pauseConfig: pauseConfigPda,
             authority: studentUser.publicKey,
           })
           .signers([studentUser])
@@ -109,6 +122,256 @@ describe("Security Audit Tests", () => {
       }
     });
 
+    it("Should reject authority rotation proposed outside the multisig", async () => {
+      const outsider = anchor.web3.Keypair.generate();
+
+      try {
+        await governanceProgram.methods
+          .proposeAuthority([outsider.publicKey], 1)
+          .accounts({
+            poaConfig: poaConfigPda,
+            universityAuthority: outsider.publicKey,
+          })
+          .signers([outsider])
+          .rpc();
+
+        expect.fail("Should have required a current authority signer");
+      } catch (error: any) {
+        expect(error.toString()).to.include("UnauthorizedAuthority");
+        console.log("✅ Correctly rejected authority rotation from an outsider");
+      }
+    });
+
+    it("Should only count validator attestations reported by the oracle", async () => {
+      const validator = anchor.web3.Keypair.generate();
+      const impostor = anchor.web3.Keypair.generate();
+      const [recValidatorPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("rec_validator"), validator.publicKey.toBuffer()],
+        governanceProgram.programId
+      );
+      const [proposalConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal_config")],
+        governanceProgram.programId
+      );
+
+      // Validators can still be added directly while proposals are not set up
+      await governanceProgram.methods
+        .addAuthorizedRecValidator(validator.publicKey, "Security Test Validator")
+        .accounts({
+          poaConfig: poaConfigPda,
+          recValidator: recValidatorPda,
+          proposalConfig: proposalConfigPda,
+          universityAuthority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        await governanceProgram.methods
+          .recordAttestation(validator.publicKey)
+          .accounts({
+            poaConfig: poaConfigPda,
+            recValidator: recValidatorPda,
+            reporter: impostor.publicKey,
+          })
+          .signers([impostor])
+          .rpc();
+
+        expect.fail("Should have required the oracle authority PDA");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedAttestationReporter");
+        console.log("✅ Correctly rejected attestation reported outside the oracle");
+      }
+    });
+
+    it("Should reject governance proposals from suspended validators", async () => {
+      // Preloaded from tests/fixtures/suspended-rec-validator.json with active = false
+      const suspended = anchor.web3.Keypair.fromSeed(new Uint8Array(32).fill(41));
+      const [recValidatorPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("rec_validator"), suspended.publicKey.toBuffer()],
+        governanceProgram.programId
+      );
+      const [proposalConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal_config")],
+        governanceProgram.programId
+      );
+      const [proposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+        governanceProgram.programId
+      );
+
+      const sig = await provider.connection.requestAirdrop(suspended.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      // Ends validator bootstrapping, so it runs after the tests that add validators directly
+      await governanceProgram.methods
+        .initializeProposals(new anchor.BN(60), new anchor.BN(60))
+        .accounts({
+          poaConfig: poaConfigPda,
+          proposalConfig: proposalConfigPda,
+          universityAuthority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        await governanceProgram.methods
+          .createProposal({ setMinRecValidators: { minValidators: 1 } }, "Lower REC quorum")
+          .accounts({
+            poaConfig: poaConfigPda,
+            recValidator: recValidatorPda,
+            proposalConfig: proposalConfigPda,
+            proposal: proposalPda,
+            proposer: suspended.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([suspended])
+          .rpc();
+
+        expect.fail("Should have required an active REC validator");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedValidator");
+        console.log("✅ Correctly rejected proposal from a suspended validator");
+      }
+    });
+
+    it("Should reject referendum votes without tokens locked before the snapshot", async () => {
+      const voter = anchor.web3.Keypair.generate();
+      // Preloaded from tests/fixtures/open-referendum.json
+      const [referendumPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("referendum"), new anchor.BN(1_000_000).toArrayLike(Buffer, "le", 8)],
+        governanceProgram.programId
+      );
+      const [tokenLockPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("token_lock"), voter.publicKey.toBuffer()],
+        governanceProgram.programId
+      );
+      const [ballotPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("referendum_ballot"), referendumPda.toBuffer(), voter.publicKey.toBuffer()],
+        governanceProgram.programId
+      );
+
+      const sig = await provider.connection.requestAirdrop(voter.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      await governanceProgram.methods
+        .openTokenLock()
+        .accounts({
+          tokenLock: tokenLockPda,
+          owner: voter.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+      try {
+        await governanceProgram.methods
+          .castReferendumVote(true)
+          .accounts({
+            referendum: referendumPda,
+            tokenLock: tokenLockPda,
+            ballot: ballotPda,
+            voter: voter.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([voter])
+          .rpc();
+
+        expect.fail("Should have required tokens locked before the referendum opened");
+      } catch (error: any) {
+        expect(error.message).to.include("NoVotingWeight");
+        console.log("✅ Correctly rejected a referendum vote without locked tokens");
+      }
+    });
+
+    it("Should reject market clearing changes outside an executed proposal", async () => {
+      // The test wallet is the market authority, but only the governance PDA may sign
+      try {
+        await tradingProgram.methods
+          .setClearingEnabled(false)
+          .accounts({
+            market: marketPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+        expect.fail("Should have required the governance authority PDA");
+      } catch (error: any) {
+        expect(error.message).to.include("ConstraintSeeds");
+        console.log("✅ Correctly rejected a direct market clearing change from the authority");
+      }
+    });
+
+    it("Should reject program registration while governance is not the upgrade authority", async () => {
+      const loaderId = new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+      const [programRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("program_record"), tradingProgram.programId.toBuffer()],
+        governanceProgram.programId
+      );
+      const [programDataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tradingProgram.programId.toBuffer()],
+        loaderId
+      );
+
+      // The test wallet deployed the programs and still holds their upgrade authority
+      try {
+        await governanceProgram.methods
+          .registerProgram(Array(32).fill(0))
+          .accounts({
+            poaConfig: poaConfigPda,
+            programRecord: programRecordPda,
+            program: tradingProgram.programId,
+            programData: programDataPda,
+            universityAuthority: provider.wallet.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+
+        expect.fail("Should have required governance as upgrade authority");
+      } catch (error: any) {
+        expect(error.message).to.include("NotUpgradeAuthority");
+        console.log("✅ Correctly rejected registering a program governance cannot upgrade");
+      }
+    });
+
+    it("Should reject trade refunds not signed by the market authority", async () => {
+      const outsider = anchor.web3.Keypair.generate();
+      // Preloaded from tests/fixtures/settled-trade-record.json
+      const tradeRecord = anchor.web3.Keypair.fromSeed(new Uint8Array(32).fill(47)).publicKey;
+      const buyer = anchor.web3.Keypair.fromSeed(new Uint8Array(32).fill(48)).publicKey;
+
+      const sig = await provider.connection.requestAirdrop(outsider.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      await tradingProgram.methods
+        .initializeMarket()
+        .accounts({
+          market: marketPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        await tradingProgram.methods
+          .refundTrade(new anchor.BN(1_000))
+          .accounts({
+            market: marketPda,
+            authority: outsider.publicKey,
+            tradeRecord,
+            buyer,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+
+        expect.fail("Should have required the governance-held market authority");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedAuthority");
+        console.log("✅ Correctly rejected a refund outside dispute resolution");
+      }
+    });
+
     it("Should require multi-signature for critical REC operations", async () => {
       // Test that REC validation requires multiple university department signatures
       const singleValidator = anchor.web3.Keypair.generate();
@@ -117,6 +380,263 @@ describe("Security Audit Tests", () => {
       console.log("✅ Multi-signature REC validation requirement verified");
       console.log("   - Sustainability Office, Engineering Dept, Facilities Mgmt required");
     });
+
+    it("Should reject registry role grants from non-admins", async () => {
+      const outsider = anchor.web3.Keypair.generate();
+
+      try {
+        await registryProgram.methods
+          .grantRole(outsider.publicKey, { meterInstaller: {} })
+          .accounts({
+            roleTable: roleTablePda,
+            authority: outsider.publicKey,
+          })
+          .signers([outsider])
+          .rpc();
+
+        expect.fail("Should have rejected self-granted role");
+      } catch (error: any) {
+        expect(error.message).to.include("MissingRole");
+        console.log("✅ Correctly rejected role grant without RegistryAdmin");
+      }
+    });
+
+    it("Should reject meter transfers without both owners or admin approval", async () => {
+      const owner = anchor.web3.Keypair.generate();
+      const recipient = anchor.web3.Keypair.generate();
+      const meterId = `TRANSFER_${Date.now()}`;
+      const userPda = (user: anchor.web3.PublicKey) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("user"), user.toBuffer()],
+          registryProgram.programId
+        )[0];
+      const [meterAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("meter"), Buffer.from(meterId)],
+        registryProgram.programId
+      );
+
+      for (const user of [owner, recipient]) {
+        const sig = await provider.connection.requestAirdrop(user.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+
+        await registryProgram.methods
+          .registerUser({ prosumer: {} })
+          .accounts({
+            registry: registryPda,
+            userAccount: userPda(user.publicKey),
+            userAuthority: user.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+      }
+
+      await registryProgram.methods
+        .registerMeter(meterId, { solar: {} }, new anchor.BN(5000), { generationOnly: {} })
+        .accounts({
+          registry: registryPda,
+          userAccount: userPda(owner.publicKey),
+          meterAccount: meterAccountPda,
+          userAuthority: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+      // The current owner alone cannot push a meter onto someone else
+      try {
+        await registryProgram.methods
+          .transferMeter()
+          .accounts({
+            roleTable: roleTablePda,
+            meterAccount: meterAccountPda,
+            fromUserAccount: userPda(owner.publicKey),
+            toUserAccount: userPda(recipient.publicKey),
+            currentOwner: owner.publicKey,
+            newOwner: null,
+            admin: null,
+          })
+          .signers([owner])
+          .rpc();
+
+        expect.fail("Should have required the recipient's signature");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedMeterTransfer");
+        console.log("✅ Correctly rejected single-signature meter transfer");
+      }
+    });
+
+    it("Should reject meter provisioning by users without an installer role", async () => {
+      const outsider = anchor.web3.Keypair.generate();
+      const meterId = `ASSIGN_${Date.now()}`;
+      const [userAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), outsider.publicKey.toBuffer()],
+        registryProgram.programId
+      );
+      const [meterAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("meter"), Buffer.from(meterId)],
+        registryProgram.programId
+      );
+
+      const sig = await provider.connection.requestAirdrop(outsider.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      await registryProgram.methods
+        .registerUser({ consumer: {} })
+        .accounts({
+          registry: registryPda,
+          userAccount: userAccountPda,
+          userAuthority: outsider.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+
+      try {
+        await registryProgram.methods
+          .assignMeter(meterId, { grid: {} }, new anchor.BN(5000), { consumptionOnly: {} })
+          .accounts({
+            registry: registryPda,
+            roleTable: roleTablePda,
+            userAccount: userAccountPda,
+            meterAccount: meterAccountPda,
+            gridNode: null,
+            authority: outsider.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+
+        expect.fail("Should have required MeterInstaller or RegistryAdmin");
+      } catch (error: any) {
+        expect(error.message).to.include("MissingRole");
+        console.log("✅ Correctly rejected meter provisioning without an installer role");
+      }
+    });
+
+    it("Should reject trades from delegates without the trading scope", async () => {
+      const owner = anchor.web3.Keypair.generate();
+      const manager = anchor.web3.Keypair.generate();
+      const [userAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), owner.publicKey.toBuffer()],
+        registryProgram.programId
+      );
+      const [delegationPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("delegation"),
+          owner.publicKey.toBuffer(),
+          manager.publicKey.toBuffer(),
+          anchor.web3.PublicKey.default.toBuffer(),
+        ],
+        registryProgram.programId
+      );
+
+      for (const user of [owner, manager]) {
+        const sig = await provider.connection.requestAirdrop(user.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      await registryProgram.methods
+        .registerUser({ prosumer: {} })
+        .accounts({
+          registry: registryPda,
+          userAccount: userAccountPda,
+          userAuthority: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+      // The facilities manager may service meters but not trade
+      await registryProgram.methods
+        .createDelegation(manager.publicKey, anchor.web3.PublicKey.default, [{ meterStatus: {} }], new anchor.BN(0))
+        .accounts({
+          userAccount: userAccountPda,
+          meterAccount: null,
+          delegation: delegationPda,
+          owner: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+      try {
+        await tradingProgram.methods
+          .createSellOrder(new anchor.BN(500), new anchor.BN(25))
+          .accounts({
+            market: marketPda,
+            delegation: delegationPda,
+            userAccount: userAccountPda,
+            authority: manager.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([manager])
+          .rpc();
+
+        expect.fail("Should have required the trading scope");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedDelegate");
+        console.log("✅ Correctly rejected trade from a delegate without trading scope");
+      }
+    });
+
+    it("Should reject orders from users without an attested campus identity", async () => {
+      const trader = anchor.web3.Keypair.generate();
+      const [userAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), trader.publicKey.toBuffer()],
+        registryProgram.programId
+      );
+
+      const sig = await provider.connection.requestAirdrop(trader.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      await registryProgram.methods
+        .registerUser({ consumer: {} })
+        .accounts({
+          registry: registryPda,
+          userAccount: userAccountPda,
+          userAuthority: trader.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([trader])
+        .rpc();
+
+      try {
+        await tradingProgram.methods
+          .createBuyOrder(new anchor.BN(100), new anchor.BN(25))
+          .accounts({
+            market: marketPda,
+            userAccount: userAccountPda,
+            authority: trader.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([trader])
+          .rpc();
+
+        expect.fail("Should have required an attested identity");
+      } catch (error: any) {
+        expect(error.message).to.include("UnattestedTrader");
+        console.log("✅ Correctly rejected order from an unattested wallet");
+      }
+    });
+
+    it("Should refuse to migrate accounts already at the current layout", async () => {
+      try {
+        await registryProgram.methods
+          .migrateAccount()
+          .accounts({
+            account: registryPda,
+            payer: provider.wallet.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+
+        expect.fail("Should have rejected a current-version account");
+      } catch (error: any) {
+        expect(error.message).to.include("AccountAlreadyMigrated");
+        console.log("✅ Correctly rejected migration of a current registry account");
+      }
+    });
   });
 
   describe("Input Validation and Sanitization", () => {
@@ -173,7 +693,7 @@ describe("Security Audit Tests", () => {
 
       // Register user first
       await registryProgram.methods
-        .registerUser({ prosumer: {} }, "Security Test Building")
+        .registerUser({ prosumer: {} })
         .accounts({
           registry: registryPda,
           userAccount: userAccountPda,
@@ -232,31 +752,176 @@ describe("Security Audit Tests", () => {
       }
     });
 
-    it("Should sanitize user location input", async () => {
-      const user = anchor.web3.Keypair.generate();
+    it("Should enforce grid topology structure", async () => {
+      const gridNodePda = (nodeId: string) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("grid_node"), Buffer.from(nodeId)],
+          registryProgram.programId
+        )[0];
+
+      // Node ids are bounded like every other seed string
+      const longNodeId = "A".repeat(33);
+      try {
+        await registryProgram.methods
+          .createGridNode(longNodeId, { campus: {} }, new anchor.BN(1_000_000))
+          .accounts({
+            roleTable: roleTablePda,
+            gridNode: gridNodePda(longNodeId.slice(0, 32)),
+            parent: null,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+        expect.fail("Should have rejected an overly long node id");
+      } catch (error: any) {
+        console.log("✅ Correctly rejected overly long node id");
+      }
+
+      // A transformer cannot hang directly off the campus
+      const campusId = `CAMPUS_${Date.now()}`;
+      await registryProgram.methods
+        .createGridNode(campusId, { campus: {} }, new anchor.BN(1_000_000))
+        .accounts({
+          roleTable: roleTablePda,
+          gridNode: gridNodePda(campusId),
+          parent: null,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const transformerId = `TX_${Date.now()}`;
+      try {
+        await registryProgram.methods
+          .createGridNode(transformerId, { transformer: {} }, new anchor.BN(250_000))
+          .accounts({
+            roleTable: roleTablePda,
+            gridNode: gridNodePda(transformerId),
+            parent: gridNodePda(campusId),
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+        expect.fail("Should have rejected a transformer directly under the campus");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidGridTopology");
+        console.log("✅ Correctly rejected out-of-order topology");
+      }
+    });
+
+    it("Should reject physically implausible meter readings", async () => {
+      const owner = anchor.web3.Keypair.generate();
+      const meterId = `GRID_${Date.now()}`;
       const [userAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("user"), user.publicKey.toBuffer()],
+        [Buffer.from("user"), owner.publicKey.toBuffer()],
+        registryProgram.programId
+      );
+      const [meterAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("meter"), Buffer.from(meterId)],
         registryProgram.programId
       );
 
-      // Test extremely long location string
-      const longLocation = "A".repeat(1000);
-      
+      await registryProgram.methods
+        .registerUser({ consumer: {} })
+        .accounts({
+          registry: registryPda,
+          userAccount: userAccountPda,
+          userAuthority: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+      // 5 kW grid connection meter
+      await registryProgram.methods
+        .registerMeter(meterId, { grid: {} }, new anchor.BN(5000), { consumptionOnly: {} })
+        .accounts({
+          registry: registryPda,
+          userAccount: userAccountPda,
+          meterAccount: meterAccountPda,
+          userAuthority: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+      // Solar readings are checked against the weather PDA of the interval they fall in
+      const readingTimestamp = Math.floor(Date.now() / 1000);
+      const [weatherPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("weather"),
+          new anchor.BN(Math.floor((readingTimestamp - 1) / 900) * 900).toArrayLike(Buffer, "le", 8),
+        ],
+        oracleProgram.programId
+      );
+
+      // Generation on a grid-type meter is impossible
+      try {
+        await oracleProgram.methods
+          .submitMeterReading(meterId, new anchor.BN(100), new anchor.BN(0), new anchor.BN(readingTimestamp))
+          .accounts({
+            oracleData: oracleConfigPda,
+            meterAccount: meterAccountPda,
+            weatherObservation: weatherPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+        expect.fail("Should have rejected generation on a grid meter");
+      } catch (error: any) {
+        expect(error.message).to.include("ImpossibleReading");
+        console.log("✅ Correctly rejected generation on a grid meter");
+      }
+
+      // 10 MWh in one interval far exceeds 5 kW × 15 min
+      try {
+        await oracleProgram.methods
+          .submitMeterReading(meterId, new anchor.BN(0), new anchor.BN(10_000_000), new anchor.BN(readingTimestamp))
+          .accounts({
+            oracleData: oracleConfigPda,
+            meterAccount: meterAccountPda,
+            weatherObservation: weatherPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+        expect.fail("Should have required quarantine for over-capacity reading");
+      } catch (error: any) {
+        expect(error.message).to.include("ReadingExceedsCapacity");
+        console.log("✅ Over-capacity reading routed to quarantine");
+      }
+
+      // The retired OracleService role can no longer be granted
+      const impostor = anchor.web3.Keypair.generate();
       try {
         await registryProgram.methods
-          .registerUser({ consumer: {} }, longLocation)
+          .grantRole(impostor.publicKey, { oracleService: {} })
           .accounts({
-            registry: registryPda,
-            userAccount: userAccountPda,
-            userAuthority: user.publicKey,
-            systemProgram: anchor.web3.SystemProgram.programId,
+            roleTable: roleTablePda,
+            authority: provider.wallet.publicKey,
           })
-          .signers([user])
           .rpc();
-        
-        console.log("⚠️  Location length validation may need enhancement");
+
+        expect.fail("Should have rejected granting a retired role");
       } catch (error: any) {
-        console.log("✅ Correctly rejected overly long location");
+        expect(error.message).to.include("RoleRetired");
+        console.log("✅ Correctly rejected granting the retired OracleService role");
+      }
+
+      // Meter totals can only be written through the oracle program's PDA
+      try {
+        await registryProgram.methods
+          .updateMeterReading(new anchor.BN(1_000_000), new anchor.BN(0), new anchor.BN(Date.now() / 1000))
+          .accounts({
+            meterAccount: meterAccountPda,
+            oracleAuthority: impostor.publicKey,
+          })
+          .signers([impostor])
+          .rpc();
+
+        expect.fail("Should have rejected meter update outside the oracle");
+      } catch (error: any) {
+        expect(error.message).to.include("ConstraintSeeds");
+        console.log("✅ Correctly rejected direct meter reading update");
       }
     });
   });
@@ -285,7 +950,7 @@ describe("Security Audit Tests", () => {
         );
 
         const promise = registryProgram.methods
-          .registerUser({ consumer: {} }, `Concurrent User ${i}`)
+          .registerUser({ consumer: {} })
           .accounts({
             registry: registryPda,
             userAccount: userAccountPda,
@@ -309,17 +974,18 @@ describe("Security Audit Tests", () => {
 
   describe("Emergency and Recovery Mechanisms", () => {
     it("Should test emergency pause functionality", async () => {
-      // Test that emergency pause stops all trading operations
+      // Test that a trading pause stops trading operations only
       await governanceProgram.methods
-        .emergencyPause()
+        .emergencyPause({ trading: {} })
         .accounts({
           poaConfig: poaConfigPda,
+          pauseConfig: pauseConfigPda,
           authority: provider.wallet.publicKey,
         })
         .rpc();
 
-      const poaConfig = await governanceProgram.account.poAConfig.fetch(poaConfigPda);
-      expect(poaConfig.emergencyPaused).to.be.true;
+      const pauseConfig = await governanceProgram.account.pauseConfig.fetch(pauseConfigPda);
+      expect(pauseConfig.pausedScopes).to.equal(1 << 1);
 
       console.log("✅ Emergency pause mechanism verified");
 
@@ -332,7 +998,7 @@ describe("Security Audit Tests", () => {
 
       // This should work as registry is not affected by trading pause
       await registryProgram.methods
-        .registerUser({ consumer: {} }, "Emergency Test User")
+        .registerUser({ consumer: {} })
         .accounts({
           registry: registryPda,
           userAccount: userAccountPda,
@@ -344,13 +1010,17 @@ describe("Security Audit Tests", () => {
 
       // Resume operations
       await governanceProgram.methods
-        .emergencyResume()
+        .emergencyUnpause({ trading: {} })
         .accounts({
           poaConfig: poaConfigPda,
+          pauseConfig: pauseConfigPda,
           authority: provider.wallet.publicKey,
         })
         .rpc();
 
+      const resumed = await governanceProgram.account.pauseConfig.fetch(pauseConfigPda);
+      expect(resumed.pausedScopes).to.equal(0);
+
       console.log("✅ Emergency recovery mechanism verified");
     });
 
@@ -397,10 +1067,10 @@ describe("Security Audit Tests", () => {
     it("Should validate PoA validator security", async () => {
       // Test validator authentication and authorization
       const poaConfig = await governanceProgram.account.poAConfig.fetch(poaConfigPda);
-      expect(poaConfig.authorizedRecValidators.length).to.be.greaterThanOrEqual(3);
+      expect(poaConfig.recValidatorCount).to.be.greaterThanOrEqual(3);
 
       console.log("✅ PoA validator security verified:");
-      console.log(`   - ${poaConfig.authorizedRecValidators.length} authorized university validators`);
+      console.log(`   - ${poaConfig.recValidatorCount} authorized university validators`);
       console.log("   - Multi-signature consensus required for critical operations");
       console.log("   - University IT controls all validator nodes");
     });